@@ -0,0 +1,190 @@
+//! A mutex which makes tasks wait asynchronously for the lock, instead of
+//! blocking the whole thread.
+//!
+//! Waiting tasks queue up in order. When the lock is released while someone
+//! is waiting, it isn't unlocked at all: ownership passes straight to the
+//! first task in the queue, which is then woken to collect it. A task which
+//! happened to call [`Mutex::lock`] at just the right moment can't barge in
+//! ahead of tasks which have been waiting longer.
+//!
+//! ```
+//! use async_intuition::basics::{async_mutex::Mutex, executor::Executor, yield_now};
+//!
+//! let counter = Mutex::new(0);
+//! let mut executor = Executor::new();
+//! for _ in 0..2 {
+//!     executor.spawn(async {
+//!         for _ in 0..100 {
+//!             let mut count = counter.lock().await;
+//!             let read = *count;
+//!             // hold the lock across an await, giving the other task a
+//!             // chance to try to sneak in.
+//!             yield_now().await;
+//!             *count = read + 1;
+//!         }
+//!     });
+//! }
+//! executor.run();
+//! drop(executor);
+//! assert_eq!(counter.into_inner(), 200);
+//! ```
+
+use std::{
+    cell::UnsafeCell,
+    collections::VecDeque,
+    future::Future,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    sync,
+    task::{Context, Poll, Waker},
+};
+
+pub struct Mutex<T> {
+    state: sync::Mutex<State>,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: `value` is only ever reached through a `MutexGuard`, and only one
+// guard can exist at a time.
+unsafe impl<T: Send> Send for Mutex<T> {}
+unsafe impl<T: Send> Sync for Mutex<T> {}
+
+#[derive(Default)]
+struct State {
+    locked: bool,
+    // when the lock is released while tasks are waiting, it stays locked and
+    // the front waiter's id moves here, for it to pick up when it is polled.
+    handed_to: Option<u64>,
+    waiters: VecDeque<(u64, Waker)>,
+    next_id: u64,
+}
+
+impl State {
+    fn release(&mut self) {
+        match self.waiters.pop_front() {
+            Some((id, waker)) => {
+                self.handed_to = Some(id);
+                waker.wake();
+            }
+            None => self.locked = false,
+        }
+    }
+}
+
+pub struct MutexGuard<'a, T> {
+    mutex: &'a Mutex<T>,
+    // a guard hands out `&T` and `&mut T`, so it should only be `Sync` when
+    // `T` is, as with a `&mut T`.
+    _marker: PhantomData<&'a mut T>,
+}
+
+struct Lock<'a, T> {
+    mutex: &'a Mutex<T>,
+    // set once this lock attempt is queued up.
+    id: Option<u64>,
+}
+
+impl<T> Mutex<T> {
+    pub fn new(value: T) -> Self {
+        Mutex {
+            state: sync::Mutex::default(),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn lock(&self) -> impl Future<Output = MutexGuard<'_, T>> {
+        Lock {
+            mutex: self,
+            id: None,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<'a, T> Future for Lock<'a, T> {
+    type Output = MutexGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mutex = self.mutex;
+        let mut state = mutex.state.lock().unwrap();
+        match self.id {
+            Some(id) if state.handed_to == Some(id) => {
+                state.handed_to = None;
+                self.id = None;
+                Poll::Ready(MutexGuard::new(mutex))
+            }
+            Some(id) => {
+                // still queued: just make sure the right task gets woken.
+                let (_, waker) = state
+                    .waiters
+                    .iter_mut()
+                    .find(|(other, _)| *other == id)
+                    .unwrap();
+                waker.clone_from(cx.waker());
+                Poll::Pending
+            }
+            None if !state.locked => {
+                state.locked = true;
+                Poll::Ready(MutexGuard::new(mutex))
+            }
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back((id, cx.waker().clone()));
+                self.id = Some(id);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Lock<'_, T> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut state = self.mutex.state.lock().unwrap();
+        if state.handed_to == Some(id) {
+            // the lock was handed over, but nobody is left to use it.
+            state.handed_to = None;
+            state.release();
+        } else {
+            state.waiters.retain(|(other, _)| *other != id);
+        }
+    }
+}
+
+impl<'a, T> MutexGuard<'a, T> {
+    fn new(mutex: &'a Mutex<T>) -> Self {
+        MutexGuard {
+            mutex,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for MutexGuard<'_, T> {
+    fn drop(&mut self) {
+        self.mutex.state.lock().unwrap().release();
+    }
+}
+
+impl<T> Deref for MutexGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: holding the guard means holding the lock.
+        unsafe { &*self.mutex.value.get() }
+    }
+}
+
+impl<T> DerefMut for MutexGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: holding the guard means holding the lock.
+        unsafe { &mut *self.mutex.value.get() }
+    }
+}
@@ -0,0 +1,350 @@
+//! A reader-writer lock which makes tasks wait asynchronously, letting any
+//! number of readers in at once, or a single writer.
+//!
+//! The fairness policy is writer-preferring, without letting writers starve
+//! readers either:
+//!
+//! - a new reader only gets straight in if no writer holds the lock *or is
+//!   waiting for it*. Otherwise it queues, so a steady churn of readers can't
+//!   hold a waiting writer off forever.
+//! - a writer only gets straight in if nobody holds the lock at all.
+//! - when the last reader leaves, the lock passes to the longest-waiting
+//!   writer.
+//! - when a writer leaves, every reader waiting at that moment is let in
+//!   together. Only if there are none does the lock pass to the next writer,
+//!   so a queue of writers can't hold readers off forever.
+//!
+//! As with the [`async_mutex`](super::async_mutex), the lock is handed over
+//! rather than released while anyone is waiting, so latecomers can't barge
+//! in ahead of the queue.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! use async_intuition::basics::{async_rwlock::RwLock, executor::Executor, yield_now};
+//!
+//! let lock = RwLock::new(0);
+//! let (reading, most_reading) = (Cell::new(0), Cell::new(0));
+//! let mut executor = Executor::new();
+//! for _ in 0..3 {
+//!     executor.spawn(async {
+//!         let value = lock.read().await;
+//!         reading.set(reading.get() + 1);
+//!         most_reading.set(most_reading.get().max(reading.get()));
+//!         yield_now().await;
+//!         assert_eq!(*value, 0);
+//!         reading.set(reading.get() - 1);
+//!     });
+//! }
+//! executor.run();
+//! // all three readers held the lock at the same time.
+//! assert_eq!(most_reading.get(), 3);
+//! ```
+//!
+//! A writer has the lock to itself, and readers arriving while it waits queue
+//! up behind it:
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! use async_intuition::basics::{async_rwlock::RwLock, executor::Executor, yield_now};
+//!
+//! let lock = RwLock::new(0);
+//! let log = RefCell::new(Vec::new());
+//! let mut executor = Executor::new();
+//! let (lock, log) = (&lock, &log);
+//! executor.spawn(async move {
+//!     let value = lock.read().await;
+//!     log.borrow_mut().push("first read");
+//!     for _ in 0..3 {
+//!         yield_now().await;
+//!     }
+//!     log.borrow_mut().push(if *value == 0 { "first read done" } else { "torn" });
+//! });
+//! executor.spawn(async move {
+//!     let mut value = lock.write().await;
+//!     log.borrow_mut().push("write");
+//!     *value += 1;
+//!     yield_now().await;
+//!     log.borrow_mut().push("write done");
+//! });
+//! executor.spawn(async move {
+//!     // arrives while the writer is waiting.
+//!     yield_now().await;
+//!     let value = lock.read().await;
+//!     log.borrow_mut().push(if *value == 1 { "second read" } else { "barged in" });
+//! });
+//! executor.run();
+//!
+//! assert_eq!(
+//!     log.take(),
+//!     ["first read", "first read done", "write", "write done", "second read"]
+//! );
+//! ```
+
+use std::{
+    cell::UnsafeCell,
+    collections::{HashSet, VecDeque},
+    future::Future,
+    marker::PhantomData,
+    ops::{Deref, DerefMut},
+    pin::Pin,
+    sync,
+    task::{Context, Poll, Waker},
+};
+
+pub struct RwLock<T> {
+    state: sync::Mutex<State>,
+    value: UnsafeCell<T>,
+}
+
+// SAFETY: `value` is only ever reached through the guards, and either there
+// is at most one write guard or there are only read guards.
+unsafe impl<T: Send> Send for RwLock<T> {}
+unsafe impl<T: Send + Sync> Sync for RwLock<T> {}
+
+#[derive(Default)]
+struct State {
+    // readers currently holding the lock, including any it was handed to
+    // which haven't been polled to find out yet.
+    readers: usize,
+    // likewise for the writer.
+    writer: bool,
+    // the ids of waiters the lock has been handed to, for them to pick up
+    // when they are next polled.
+    granted: HashSet<u64>,
+    waiting_readers: VecDeque<(u64, Waker)>,
+    waiting_writers: VecDeque<(u64, Waker)>,
+    next_id: u64,
+}
+
+impl State {
+    fn release_read(&mut self) {
+        self.readers -= 1;
+        if self.readers == 0 {
+            self.grant_writer();
+        }
+    }
+
+    fn release_write(&mut self) {
+        self.writer = false;
+        if self.waiting_readers.is_empty() {
+            self.grant_writer();
+        } else {
+            self.grant_readers();
+        }
+    }
+
+    fn grant_writer(&mut self) {
+        if let Some((id, waker)) = self.waiting_writers.pop_front() {
+            self.writer = true;
+            self.granted.insert(id);
+            waker.wake();
+        }
+    }
+
+    fn grant_readers(&mut self) {
+        for (id, waker) in self.waiting_readers.drain(..) {
+            self.readers += 1;
+            self.granted.insert(id);
+            waker.wake();
+        }
+    }
+
+    fn enqueue(queue: &mut VecDeque<(u64, Waker)>, next_id: &mut u64, waker: &Waker) -> u64 {
+        let id = *next_id;
+        *next_id += 1;
+        queue.push_back((id, waker.clone()));
+        id
+    }
+
+    fn update_waker(queue: &mut VecDeque<(u64, Waker)>, id: u64, waker: &Waker) {
+        let (_, stored) = queue.iter_mut().find(|(other, _)| *other == id).unwrap();
+        stored.clone_from(waker);
+    }
+}
+
+pub struct RwLockReadGuard<'a, T> {
+    lock: &'a RwLock<T>,
+}
+
+pub struct RwLockWriteGuard<'a, T> {
+    lock: &'a RwLock<T>,
+    // hands out `&mut T`, so should only be `Sync` when `T` is.
+    _marker: PhantomData<&'a mut T>,
+}
+
+struct Read<'a, T> {
+    lock: &'a RwLock<T>,
+    // set once this read attempt is queued up.
+    id: Option<u64>,
+}
+
+struct Write<'a, T> {
+    lock: &'a RwLock<T>,
+    // set once this write attempt is queued up.
+    id: Option<u64>,
+}
+
+impl<T> RwLock<T> {
+    pub fn new(value: T) -> Self {
+        RwLock {
+            state: sync::Mutex::default(),
+            value: UnsafeCell::new(value),
+        }
+    }
+
+    pub fn read(&self) -> impl Future<Output = RwLockReadGuard<'_, T>> {
+        Read {
+            lock: self,
+            id: None,
+        }
+    }
+
+    pub fn write(&self) -> impl Future<Output = RwLockWriteGuard<'_, T>> {
+        Write {
+            lock: self,
+            id: None,
+        }
+    }
+
+    pub fn into_inner(self) -> T {
+        self.value.into_inner()
+    }
+}
+
+impl<'a, T> Future for Read<'a, T> {
+    type Output = RwLockReadGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let lock = self.lock;
+        let mut state = lock.state.lock().unwrap();
+        let state = &mut *state;
+        match self.id {
+            Some(id) if state.granted.remove(&id) => {
+                self.id = None;
+                Poll::Ready(RwLockReadGuard { lock })
+            }
+            Some(id) => {
+                State::update_waker(&mut state.waiting_readers, id, cx.waker());
+                Poll::Pending
+            }
+            None if !state.writer && state.waiting_writers.is_empty() => {
+                state.readers += 1;
+                Poll::Ready(RwLockReadGuard { lock })
+            }
+            None => {
+                let id = State::enqueue(&mut state.waiting_readers, &mut state.next_id, cx.waker());
+                self.id = Some(id);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<'a, T> Future for Write<'a, T> {
+    type Output = RwLockWriteGuard<'a, T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let lock = self.lock;
+        let mut state = lock.state.lock().unwrap();
+        let state = &mut *state;
+        match self.id {
+            Some(id) if state.granted.remove(&id) => {
+                self.id = None;
+                Poll::Ready(RwLockWriteGuard::new(lock))
+            }
+            Some(id) => {
+                State::update_waker(&mut state.waiting_writers, id, cx.waker());
+                Poll::Pending
+            }
+            None if !state.writer && state.readers == 0 => {
+                state.writer = true;
+                Poll::Ready(RwLockWriteGuard::new(lock))
+            }
+            None => {
+                let id = State::enqueue(&mut state.waiting_writers, &mut state.next_id, cx.waker());
+                self.id = Some(id);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl<T> Drop for Read<'_, T> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut state = self.lock.state.lock().unwrap();
+        if state.granted.remove(&id) {
+            // the lock was handed over, but nobody is left to use it.
+            state.release_read();
+        } else {
+            state.waiting_readers.retain(|(other, _)| *other != id);
+        }
+    }
+}
+
+impl<T> Drop for Write<'_, T> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut state = self.lock.state.lock().unwrap();
+        if state.granted.remove(&id) {
+            state.release_write();
+        } else {
+            state.waiting_writers.retain(|(other, _)| *other != id);
+            // readers may have been queued only because this writer was
+            // waiting.
+            if !state.writer && state.waiting_writers.is_empty() {
+                state.grant_readers();
+            }
+        }
+    }
+}
+
+impl<'a, T> RwLockWriteGuard<'a, T> {
+    fn new(lock: &'a RwLock<T>) -> Self {
+        RwLockWriteGuard {
+            lock,
+            _marker: PhantomData,
+        }
+    }
+}
+
+impl<T> Drop for RwLockReadGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.lock().unwrap().release_read();
+    }
+}
+
+impl<T> Drop for RwLockWriteGuard<'_, T> {
+    fn drop(&mut self) {
+        self.lock.state.lock().unwrap().release_write();
+    }
+}
+
+impl<T> Deref for RwLockReadGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: holding a read guard means no writer holds the lock.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> Deref for RwLockWriteGuard<'_, T> {
+    type Target = T;
+
+    fn deref(&self) -> &T {
+        // SAFETY: holding the write guard means holding the lock alone.
+        unsafe { &*self.lock.value.get() }
+    }
+}
+
+impl<T> DerefMut for RwLockWriteGuard<'_, T> {
+    fn deref_mut(&mut self) -> &mut T {
+        // SAFETY: holding the write guard means holding the lock alone.
+        unsafe { &mut *self.lock.value.get() }
+    }
+}
@@ -0,0 +1,143 @@
+//! Hold tasks back until a set number of them have arrived, then release them
+//! all together.
+//!
+//! Every [`Barrier::wait`] before the `n`th queues up its waker and stays
+//! pending. The `n`th arrival wakes the lot and starts a new generation, so
+//! the same barrier can be waited on again straight away. Exactly one task
+//! per generation, the one which arrived last, is told it is the leader.
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! # use futures_lite::future::yield_now;
+//! use async_intuition::basics::{barrier::Barrier, executor::Executor};
+//!
+//! let barrier = Barrier::new(3);
+//! let log = RefCell::new(Vec::new());
+//! let mut executor = Executor::new();
+//! for task in 0..3 {
+//!     let (barrier, log) = (&barrier, &log);
+//!     executor.spawn(async move {
+//!         // two rounds, with each task taking its own time to arrive.
+//!         for round in 0..2 {
+//!             for _ in 0..task {
+//!                 yield_now().await;
+//!             }
+//!             log.borrow_mut().push(("arrive", round));
+//!             if barrier.wait().await.is_leader() {
+//!                 log.borrow_mut().push(("lead", round));
+//!             }
+//!             log.borrow_mut().push(("leave", round));
+//!         }
+//!     });
+//! }
+//! executor.run();
+//!
+//! let log = log.take();
+//! let position = |entry| log.iter().position(|&e| e == entry).unwrap();
+//! for round in 0..2 {
+//!     // nobody leaves a round until everybody has arrived.
+//!     let arrived = log.iter().rposition(|&e| e == ("arrive", round)).unwrap();
+//!     assert!(arrived < position(("leave", round)));
+//!     assert_eq!(log.iter().filter(|&&e| e == ("lead", round)).count(), 1);
+//!     assert_eq!(log.iter().filter(|&&e| e == ("leave", round)).count(), 3);
+//! }
+//! ```
+//!
+//! Dropping a [`BarrierWait`] after it has arrived doesn't take the arrival
+//! back: the barrier still counts it towards the current generation.
+
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Debug)]
+pub struct Barrier {
+    n: usize,
+    state: Mutex<State>,
+}
+
+#[derive(Debug)]
+struct State {
+    generation: u64,
+    // the wakers of everyone in the current generation who is waiting, in
+    // order of arrival.
+    waiters: VecDeque<Waker>,
+}
+
+/// What a [`Barrier::wait`] resolves to.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BarrierWaitResult {
+    is_leader: bool,
+}
+
+impl BarrierWaitResult {
+    /// Whether this was the last task to arrive, and so the one which released
+    /// the others.
+    pub fn is_leader(&self) -> bool {
+        self.is_leader
+    }
+}
+
+#[derive(Debug)]
+pub struct BarrierWait<'a> {
+    barrier: &'a Barrier,
+    // set on arrival: the generation waited on, and the position of our waker
+    // in `waiters`.
+    arrived: Option<(u64, usize)>,
+}
+
+impl Barrier {
+    /// A barrier releasing `n` tasks at a time. As with
+    /// [`std::sync::Barrier`], an `n` of zero behaves like one.
+    pub fn new(n: usize) -> Self {
+        Barrier {
+            n,
+            state: Mutex::new(State {
+                generation: 0,
+                waiters: VecDeque::new(),
+            }),
+        }
+    }
+
+    pub fn wait(&self) -> BarrierWait<'_> {
+        BarrierWait {
+            barrier: self,
+            arrived: None,
+        }
+    }
+}
+
+impl Future for BarrierWait<'_> {
+    type Output = BarrierWaitResult;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let barrier = self.barrier;
+        let mut state = barrier.state.lock().unwrap();
+        match self.arrived {
+            // released: the generation has moved on without us.
+            Some((generation, _)) if generation != state.generation => {
+                Poll::Ready(BarrierWaitResult { is_leader: false })
+            }
+            Some((_, slot)) => {
+                state.waiters[slot].clone_from(cx.waker());
+                Poll::Pending
+            }
+            None if state.waiters.len() + 1 >= barrier.n => {
+                state.generation += 1;
+                for waker in state.waiters.drain(..) {
+                    waker.wake();
+                }
+                Poll::Ready(BarrierWaitResult { is_leader: true })
+            }
+            None => {
+                self.arrived = Some((state.generation, state.waiters.len()));
+                state.waiters.push_back(cx.waker().clone());
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,96 @@
+//! The simplest executor: run a single future to completion on the current
+//! thread, parking the thread whenever the future is pending.
+//!
+//! The waker handed to the future just unparks the blocked thread. See
+//! [`waker`](super::waker) for how it gets turned into a [`Waker`].
+//!
+//! ```
+//! # use std::time::{Duration, Instant};
+//! use async_intuition::basics::{block_on, sleep};
+//!
+//! assert_eq!(block_on(async { 5 }), 5);
+//!
+//! let start = Instant::now();
+//! block_on(sleep(Duration::from_millis(20)));
+//! assert!(start.elapsed() >= Duration::from_millis(20));
+//! ```
+//!
+//! [`block_on_timeout`] gives up once a duration has passed, rather than
+//! blocking for as long as the future takes:
+//!
+//! ```
+//! # use std::time::{Duration, Instant};
+//! # use futures_lite::future::pending;
+//! use async_intuition::basics::block_on::block_on_timeout;
+//!
+//! let long = Duration::from_secs(60);
+//! assert_eq!(block_on_timeout(async { 5 }, long), Some(5));
+//!
+//! let start = Instant::now();
+//! let short = Duration::from_millis(20);
+//! assert_eq!(block_on_timeout(pending::<()>(), short), None);
+//! assert!(start.elapsed() >= short);
+//! ```
+//!
+//! [`Waker`]: std::task::Waker
+
+use std::{
+    future::Future,
+    pin::pin,
+    sync::Arc,
+    task::{Context, Poll, Wake},
+    thread::{self, Thread},
+    time::{Duration, Instant},
+};
+
+use super::waker::waker_from_arc;
+
+pub fn block_on<F: Future>(fut: F) -> F::Output {
+    let mut fut = pin!(fut);
+    let waker = waker_from_arc(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        match fut.as_mut().poll(&mut cx) {
+            Poll::Ready(output) => return output,
+            // `park` may return without anyone having called `wake` (a
+            // spurious wakeup), which is harmless: the future just gets polled
+            // again and says it's still pending.
+            Poll::Pending => thread::park(),
+        }
+    }
+}
+
+/// Like [`block_on`], but gives up and returns `None` if the future is still
+/// pending once `timeout` has passed. The future is dropped unfinished.
+pub fn block_on_timeout<F: Future>(fut: F, timeout: Duration) -> Option<F::Output> {
+    let deadline = Instant::now() + timeout;
+    let mut fut = pin!(fut);
+    let waker = waker_from_arc(Arc::new(ThreadWaker(thread::current())));
+    let mut cx = Context::from_waker(&waker);
+    loop {
+        if let Poll::Ready(output) = fut.as_mut().poll(&mut cx) {
+            return Some(output);
+        }
+        // checked between polls, so a future which is ready by the time the
+        // deadline passes still gets the chance to say so.
+        let now = Instant::now();
+        if now >= deadline {
+            return None;
+        }
+        // as with `park`, this may return early. Whether it was a wakeup, a
+        // spurious return or the deadline, the loop polls again and checks.
+        thread::park_timeout(deadline - now);
+    }
+}
+
+struct ThreadWaker(Thread);
+
+impl Wake for ThreadWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.0.unpark();
+    }
+}
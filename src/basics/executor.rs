@@ -0,0 +1,286 @@
+//! A single-threaded executor which can run many tasks at once.
+//!
+//! Where [`block_on`](fn@super::block_on) drives one future, an [`Executor`]
+//! keeps a list of tasks and a queue of which of them are ready to be polled.
+//! Each task is polled with its own waker, made once when the task is
+//! spawned, which pushes the task's index back onto that queue and unparks
+//! the thread running the executor. Handing a task the same waker every time
+//! lets the futures inside it tell, with [`Waker::will_wake`], that the
+//! waker they kept from their last poll is still the right one.
+//!
+//! ```
+//! # use std::{cell::Cell, time::Duration};
+//! use async_intuition::basics::{executor::Executor, sleep};
+//!
+//! let finished = Cell::new(0);
+//! let mut executor = Executor::new();
+//! for millis in [30, 10, 20] {
+//!     let finished = &finished;
+//!     executor.spawn(async move {
+//!         sleep(Duration::from_millis(millis)).await;
+//!         finished.set(finished.get() + 1);
+//!     });
+//! }
+//!
+//! executor.run();
+//! assert_eq!(finished.get(), 3);
+//! ```
+//!
+//! However many times a task is polled, it gets the same waker:
+//!
+//! ```
+//! # use std::{cell::{Cell, RefCell}, task::Waker};
+//! use async_intuition::{
+//!     basics::{executor::Executor, yield_now},
+//!     two_ways::with_context::manual::with_context,
+//! };
+//!
+//! let last: RefCell<Option<Waker>> = RefCell::default();
+//! let (polls, same) = (Cell::new(0), Cell::new(true));
+//! let mut executor = Executor::new();
+//! executor.spawn(with_context(
+//!     async {
+//!         for _ in 0..3 {
+//!             yield_now().await;
+//!         }
+//!     },
+//!     |waker: &Waker| {
+//!         polls.set(polls.get() + 1);
+//!         if let Some(last) = last.replace(Some(waker.clone())) {
+//!             same.set(same.get() && last.will_wake(waker));
+//!         }
+//!     },
+//! ));
+//! executor.run();
+//! assert_eq!(polls.get(), 4);
+//! assert!(same.get());
+//! ```
+//!
+//! [`spawn`](Executor::spawn) returns a [`JoinHandle`], which is a future
+//! resolving to the task's output, so one task can await another. A panic
+//! inside a task is caught at the task boundary, and becomes the `Err` its
+//! handle resolves to. If the handle has already been dropped, the panic has
+//! nowhere to go, so it carries on unwinding out of [`run`](Executor::run).
+//!
+//! ```
+//! # use std::{cell::Cell, time::Duration};
+//! use async_intuition::basics::{executor::{Executor, JoinError}, sleep};
+//!
+//! let seen = Cell::new(None);
+//! let panicked = Cell::new(false);
+//! let mut executor = Executor::new();
+//!
+//! let answer = executor.spawn(async {
+//!     sleep(Duration::from_millis(10)).await;
+//!     42
+//! });
+//! executor.spawn(async { seen.set(Some(answer.await.unwrap())) });
+//!
+//! let boom = executor.spawn(async { panic!("boom") });
+//! executor.spawn(async {
+//!     let err = boom.await.unwrap_err();
+//!     panicked.set(matches!(err, JoinError::Panicked(_)));
+//! });
+//!
+//! executor.run();
+//! assert_eq!(seen.get(), Some(42));
+//! assert!(panicked.get());
+//! ```
+
+use std::{
+    any::Any,
+    cell::RefCell,
+    collections::VecDeque,
+    fmt,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    rc::Rc,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Wake, Waker},
+    thread::{self, Thread},
+};
+
+use futures_lite::FutureExt;
+
+use super::{
+    oneshot::{self, Canceled},
+    waker::waker_from_arc,
+};
+
+type Task<'a> = Pin<Box<dyn Future<Output = ()> + 'a>>;
+
+/// Tasks may borrow from anything which outlives the executor, since they
+/// are all finished (or dropped) by the time it goes away.
+#[derive(Default)]
+pub struct Executor<'a> {
+    // indexed by task id, each with the waker it is always polled with.
+    // Finished tasks leave a `None` behind so that the ids of the others
+    // stay put.
+    tasks: Vec<Option<(Task<'a>, Waker)>>,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+    // tasks spawned through a `Spawner`, which are given an id once `run`
+    // gets round to them.
+    spawned: Rc<RefCell<Vec<Task<'a>>>>,
+}
+
+/// A handle for spawning more tasks onto an [`Executor`], even from inside
+/// one of its own tasks while it is running.
+#[derive(Clone)]
+pub struct Spawner<'a> {
+    spawned: Rc<RefCell<Vec<Task<'a>>>>,
+}
+
+impl<'a> Spawner<'a> {
+    /// Like [`Executor::spawn`]. The task is first polled once the task
+    /// currently being polled (if any) has returned.
+    pub fn spawn<T: 'a>(&self, fut: impl Future<Output = T> + 'a) -> JoinHandle<T> {
+        let (task, handle) = into_task(fut);
+        self.spawned.borrow_mut().push(task);
+        handle
+    }
+}
+
+impl fmt::Debug for Spawner<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Spawner").finish_non_exhaustive()
+    }
+}
+
+// wraps `fut` so that its output, or its panic, ends up with the handle.
+fn into_task<'a, T: 'a>(fut: impl Future<Output = T> + 'a) -> (Task<'a>, JoinHandle<T>) {
+    let (tx, rx) = oneshot::channel();
+    let task = async move {
+        let result = AssertUnwindSafe(fut).catch_unwind().await;
+        // a detached task's panic mustn't vanish without a trace.
+        if let Err(Err(payload)) = tx.send(result) {
+            panic::resume_unwind(payload);
+        }
+    };
+    (Box::pin(task), JoinHandle { rx })
+}
+
+struct TaskWaker {
+    index: usize,
+    ready: Arc<Mutex<VecDeque<usize>>>,
+    // the thread running the executor, which may be parked waiting for work.
+    thread: Thread,
+}
+
+impl Wake for TaskWaker {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        self.ready.lock().unwrap().push_back(self.index);
+        self.thread.unpark();
+    }
+}
+
+impl<'a> Executor<'a> {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn spawn<T: 'a>(&mut self, fut: impl Future<Output = T> + 'a) -> JoinHandle<T> {
+        let (task, handle) = into_task(fut);
+        self.push(task);
+        handle
+    }
+
+    pub fn spawner(&self) -> Spawner<'a> {
+        Spawner {
+            spawned: Rc::clone(&self.spawned),
+        }
+    }
+
+    // an `Executor` isn't `Send`, so the thread spawning the task is the one
+    // which will run it.
+    fn push(&mut self, task: Task<'a>) {
+        let index = self.tasks.len();
+        let waker = waker_from_arc(Arc::new(TaskWaker {
+            index,
+            ready: Arc::clone(&self.ready),
+            thread: thread::current(),
+        }));
+        self.tasks.push(Some((task, waker)));
+        // every task gets polled at least once, without waiting to be woken.
+        self.ready.lock().unwrap().push_back(index);
+    }
+
+    /// Poll tasks as they become ready, until every task has completed.
+    pub fn run(&mut self) {
+        loop {
+            // spawners only ever run on this thread, inside a poll, so
+            // anything spawned since the last poll is already here.
+            for task in self.spawned.take() {
+                self.push(task);
+            }
+            if !self.tasks.iter().any(Option::is_some) {
+                break;
+            }
+
+            let Some(index) = self.ready.lock().unwrap().pop_front() else {
+                // nothing to do until some task is woken.
+                thread::park();
+                continue;
+            };
+            // a task can be woken several times before it is polled, or after
+            // it completes, so its index may turn up more than needed.
+            let Some((task, waker)) = &mut self.tasks[index] else {
+                continue;
+            };
+
+            if let Poll::Ready(()) = task.as_mut().poll(&mut Context::from_waker(waker)) {
+                self.tasks[index] = None;
+            }
+        }
+    }
+}
+
+/// Why a [`JoinHandle`] has no output to resolve to.
+#[derive(Debug)]
+pub enum JoinError {
+    /// The task panicked, with this payload.
+    Panicked(Box<dyn Any + Send>),
+    /// The executor was dropped before the task could finish.
+    Canceled,
+}
+
+impl fmt::Display for JoinError {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        match self {
+            JoinError::Panicked(_) => f.write_str("task panicked"),
+            JoinError::Canceled => f.write_str("task dropped before completing"),
+        }
+    }
+}
+
+impl std::error::Error for JoinError {}
+
+/// A future resolving to the output of a task spawned on an [`Executor`].
+/// Dropping it detaches the task, which still runs to completion.
+pub struct JoinHandle<T> {
+    // written by the task itself once its future has finished, one way or
+    // the other.
+    rx: oneshot::Receiver<thread::Result<T>>,
+}
+
+impl<T> fmt::Debug for JoinHandle<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("JoinHandle").finish_non_exhaustive()
+    }
+}
+
+impl<T> Future for JoinHandle<T> {
+    type Output = Result<T, JoinError>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut self.rx).poll(cx).map(|result| match result {
+            Ok(Ok(out)) => Ok(out),
+            Ok(Err(payload)) => Err(JoinError::Panicked(payload)),
+            Err(Canceled) => Err(JoinError::Canceled),
+        })
+    }
+}
@@ -0,0 +1,69 @@
+//! A timer which fires repeatedly, once every period.
+//!
+//! Each deadline is computed from the previous deadline rather than from when
+//! the previous tick was noticed. Otherwise the time it takes to get around
+//! to polling (and whatever the task does between ticks) would be added to
+//! every period, and the ticks would slowly drift later and later.
+//!
+//! If the interval isn't polled for longer than a period, the ticks it missed
+//! aren't skipped: each one completes immediately on the following calls to
+//! [`Interval::tick`], until the interval has caught up with the clock.
+//!
+//! ```
+//! # use std::time::{Duration, Instant};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::basics::interval::interval;
+//!
+//! let start = Instant::now();
+//! let mut ticks = interval(Duration::from_millis(20));
+//! block_on(async {
+//!     for _ in 0..3 {
+//!         ticks.tick().await;
+//!     }
+//! });
+//! assert!(start.elapsed() >= Duration::from_millis(60));
+//! ```
+
+use std::{
+    future::{poll_fn, Future},
+    pin::Pin,
+    task::{Context, Poll},
+    time::{Duration, Instant},
+};
+
+use super::Sleep;
+
+pub fn interval(period: Duration) -> Interval {
+    let next_deadline = Instant::now() + period;
+    Interval {
+        period,
+        next_deadline,
+        sleep: Sleep::at(next_deadline),
+    }
+}
+
+pub struct Interval {
+    period: Duration,
+    next_deadline: Instant,
+    // always sleeping until `next_deadline`.
+    sleep: Sleep,
+}
+
+impl Interval {
+    /// Wait for the next tick, returning the deadline it was scheduled for.
+    pub async fn tick(&mut self) -> Instant {
+        poll_fn(|cx| self.poll_tick(cx)).await
+    }
+
+    pub fn poll_tick(&mut self, cx: &mut Context<'_>) -> Poll<Instant> {
+        let Poll::Ready(()) = Pin::new(&mut self.sleep).poll(cx) else {
+            return Poll::Pending;
+        };
+        let deadline = self.next_deadline;
+        self.next_deadline = deadline + self.period;
+        // a deadline which has already passed completes on first poll, which
+        // is what lets a late interval catch up on its missed ticks.
+        self.sleep = Sleep::at(self.next_deadline);
+        Poll::Ready(deadline)
+    }
+}
@@ -0,0 +1,89 @@
+//! Leaf futures and the machinery underneath them. Where [`two_ways`] composes
+//! existing futures, the modules here implement futures which actually have to
+//! arrange for their own wakeups.
+//!
+//! Everything which needs a thread (the reactor, executors, and anything which
+//! blocks or locks) is only available with the `std` feature. The rest only
+//! needs `core` and `alloc`.
+//!
+//! Unlike the state machines `async` builds (see
+//! [`pin_and_suffering`](crate::pin_and_suffering)), none of the leaf futures
+//! here ever point into themselves. A [`Sleep`]'s timer lives behind an
+//! `Arc`, and a [`Ready`] only ever moves its value out. So they're all
+//! [`Unpin`], and can be polled with `Pin::new(&mut fut)` from wherever they
+//! happen to be, no `Box::pin` needed:
+//!
+//! ```
+//! use async_intuition::basics::{pending, ready, yield_now};
+//!
+//! fn assert_unpin<T: Unpin>(_: &T) {}
+//!
+//! // `Ready` is `Unpin` even when its value isn't.
+//! assert_unpin(&ready(std::marker::PhantomPinned));
+//! assert_unpin(&pending::<std::marker::PhantomPinned>());
+//! assert_unpin(&yield_now());
+//! # #[cfg(feature = "std")] {
+//! # use std::time::Duration;
+//! # use async_intuition::basics::sleep;
+//! assert_unpin(&sleep(Duration::from_secs(1)));
+//! # }
+//! ```
+//!
+//! Anything built with `async`, on the other hand, is `!Unpin` regardless of
+//! what it awaits, since the compiler can't rule out a borrow held across an
+//! `.await`:
+//!
+//! ```compile_fail
+//! use async_intuition::{basics::ready, two_ways::a_then_b::manual::a_then_b};
+//!
+//! fn assert_unpin<T: Unpin>(_: &T) {}
+//!
+//! assert_unpin(&a_then_b(ready(()), ready(())));
+//! ```
+//!
+//! [`two_ways`]: crate::two_ways
+
+#[cfg(feature = "std")]
+pub mod async_mutex;
+#[cfg(feature = "std")]
+pub mod async_rwlock;
+#[cfg(feature = "std")]
+pub mod barrier;
+#[cfg(feature = "std")]
+pub mod block_on;
+#[cfg(feature = "std")]
+pub mod executor;
+#[cfg(feature = "std")]
+pub mod interval;
+#[cfg(feature = "std")]
+pub mod mpsc;
+#[cfg(feature = "std")]
+pub mod notify;
+#[cfg(feature = "std")]
+pub mod once_cell;
+#[cfg(feature = "std")]
+pub mod oneshot;
+#[cfg(feature = "std")]
+pub mod rate_limiter;
+#[cfg(feature = "std")]
+pub mod reactor;
+pub mod ready;
+#[cfg(feature = "std")]
+pub mod scope;
+#[cfg(feature = "std")]
+pub mod semaphore;
+#[cfg(feature = "std")]
+pub mod sleep;
+#[cfg(feature = "std")]
+pub mod spawn_blocking;
+#[cfg(feature = "std")]
+mod sync;
+pub mod waker;
+pub mod yield_now;
+
+#[cfg(feature = "std")]
+pub use block_on::block_on;
+pub use ready::{pending, ready, Pending, Ready};
+#[cfg(feature = "std")]
+pub use sleep::{sleep, Sleep};
+pub use yield_now::{yield_now, YieldNow};
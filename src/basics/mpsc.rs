@@ -0,0 +1,260 @@
+//! A bounded channel with any number of senders and a single receiver.
+//!
+//! Both ends can have to wait: [`Receiver::recv`] while the buffer is empty,
+//! and [`Sender::send`] while it is full. The receiver's waker lives in a
+//! single slot, and every waiting send keeps one slot of its own in a queue,
+//! which it gives up again if it is dropped before finishing. Receiving a
+//! value makes room, so it wakes the waiting senders. Sending one wakes the
+//! receiver.
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! use async_intuition::basics::{block_on, executor::Executor, mpsc::channel};
+//!
+//! let (tx, mut rx) = channel(3);
+//! block_on(async {
+//!     for i in 0..3 {
+//!         tx.send(i).await.unwrap();
+//!     }
+//!     drop(tx);
+//!     let mut drained = Vec::new();
+//!     while let Some(i) = rx.recv().await {
+//!         drained.push(i);
+//!     }
+//!     assert_eq!(drained, [0, 1, 2]);
+//! });
+//!
+//! // with room for only one value, the second send waits for the receiver.
+//! let log = RefCell::new(Vec::new());
+//! let (tx, mut rx) = channel(1);
+//! let mut executor = Executor::new();
+//! let log_ref = &log;
+//! executor.spawn(async move {
+//!     for i in 0..2 {
+//!         tx.send(i).await.unwrap();
+//!         log_ref.borrow_mut().push(format!("sent {i}"));
+//!     }
+//! });
+//! executor.spawn(async move {
+//!     while let Some(i) = rx.recv().await {
+//!         log_ref.borrow_mut().push(format!("received {i}"));
+//!     }
+//! });
+//! executor.run();
+//! drop(executor);
+//! assert_eq!(log.into_inner(), ["sent 0", "received 0", "sent 1", "received 1"]);
+//! ```
+//!
+//! A send which is polled again while it waits updates its slot rather than
+//! taking another, so only the latest waker hears about the room:
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::Context};
+//! use async_intuition::{
+//!     basics::{block_on, mpsc::channel},
+//!     testing::RecordingWaker,
+//! };
+//!
+//! let (tx, mut rx) = channel(1);
+//! block_on(tx.send(0)).unwrap();
+//!
+//! let first = RecordingWaker::new();
+//! let second = RecordingWaker::new();
+//! let dropped = RecordingWaker::new();
+//! let mut send = pin!(tx.send(1));
+//! assert!(send.as_mut().poll(&mut Context::from_waker(&first.waker())).is_pending());
+//! assert!(send.as_mut().poll(&mut Context::from_waker(&second.waker())).is_pending());
+//! // a send dropped while waiting takes its slot with it.
+//! let mut abandoned = Box::pin(tx.send(2));
+//! assert!(abandoned.as_mut().poll(&mut Context::from_waker(&dropped.waker())).is_pending());
+//! drop(abandoned);
+//!
+//! assert_eq!(block_on(rx.recv()), Some(0));
+//! assert_eq!((first.wakes(), second.wakes(), dropped.wakes()), (0, 1, 0));
+//! ```
+
+use std::{
+    collections::VecDeque,
+    future::{poll_fn, Future},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+/// # Panics
+///
+/// If `capacity` is zero, as nothing could ever be sent.
+pub fn channel<T>(capacity: usize) -> (Sender<T>, Receiver<T>) {
+    assert!(capacity > 0, "channel capacity must be at least 1");
+    let shared = Arc::new(Mutex::new(Chan {
+        buffer: VecDeque::with_capacity(capacity),
+        capacity,
+        receiver_waker: None,
+        sender_wakers: VecDeque::new(),
+        next_id: 0,
+        senders: 1,
+        receiver_gone: false,
+    }));
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+pub struct Sender<T> {
+    shared: Arc<Mutex<Chan<T>>>,
+}
+
+pub struct Receiver<T> {
+    shared: Arc<Mutex<Chan<T>>>,
+}
+
+struct Chan<T> {
+    buffer: VecDeque<T>,
+    capacity: usize,
+    receiver_waker: Option<Waker>,
+    sender_wakers: VecDeque<(u64, Waker)>,
+    next_id: u64,
+    senders: usize,
+    receiver_gone: bool,
+}
+
+struct Sending<'a, T> {
+    sender: &'a Sender<T>,
+    value: Option<T>,
+    // set once this send is waiting for room.
+    id: Option<u64>,
+}
+
+impl<T> Chan<T> {
+    // every waiting sender is woken, not just the first: a sender which is
+    // dropped while waiting would otherwise swallow the wakeup meant for the
+    // ones behind it. Those which lose the race for space just wait again.
+    fn wake_senders(&mut self) {
+        for (_, waker) in self.sender_wakers.drain(..) {
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Sender<T> {
+    /// Send `value`, waiting for room in the buffer if need be. Hands the
+    /// value back if the receiver has been dropped.
+    pub fn send(&self, value: T) -> impl Future<Output = Result<(), T>> + '_ {
+        Sending {
+            sender: self,
+            value: Some(value),
+            id: None,
+        }
+    }
+}
+
+// nothing is ever pinned in place: the value is only moved into the buffer.
+impl<T> Unpin for Sending<'_, T> {}
+
+impl<T> Future for Sending<'_, T> {
+    type Output = Result<(), T>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut chan = self.sender.shared.lock().unwrap();
+        if chan.receiver_gone {
+            let value = self.value.take().expect("`send` polled after completion");
+            return Poll::Ready(Err(value));
+        }
+        if chan.buffer.len() == chan.capacity {
+            // waking the senders empties their slots, so this send's may be
+            // gone already if another sender took the room it was woken for.
+            let slot = self.id.and_then(|id| {
+                chan.sender_wakers
+                    .iter_mut()
+                    .find(|(other, _)| *other == id)
+            });
+            match slot {
+                Some((_, waker)) => waker.clone_from(cx.waker()),
+                None => {
+                    let id = *self.id.get_or_insert_with(|| {
+                        chan.next_id += 1;
+                        chan.next_id - 1
+                    });
+                    chan.sender_wakers.push_back((id, cx.waker().clone()));
+                }
+            }
+            return Poll::Pending;
+        }
+
+        let value = self.value.take().expect("`send` polled after completion");
+        chan.buffer.push_back(value);
+        if let Some(waker) = chan.receiver_waker.take() {
+            waker.wake();
+        }
+        Poll::Ready(Ok(()))
+    }
+}
+
+impl<T> Drop for Sending<'_, T> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut chan = self.sender.shared.lock().unwrap();
+        chan.sender_wakers.retain(|(other, _)| *other != id);
+    }
+}
+
+impl<T> Clone for Sender<T> {
+    fn clone(&self) -> Self {
+        self.shared.lock().unwrap().senders += 1;
+        Sender {
+            shared: Arc::clone(&self.shared),
+        }
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut chan = self.shared.lock().unwrap();
+        chan.senders -= 1;
+        // the last sender going away is news to a receiver waiting on an
+        // empty buffer: it can stop waiting.
+        if chan.senders == 0 {
+            if let Some(waker) = chan.receiver_waker.take() {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Receiver<T> {
+    /// Receive the next value, waiting for one if the buffer is empty.
+    /// Resolves to `None` once the buffer is empty and every sender is gone.
+    pub async fn recv(&mut self) -> Option<T> {
+        poll_fn(|cx| self.poll_recv(cx)).await
+    }
+
+    fn poll_recv(&mut self, cx: &mut Context<'_>) -> Poll<Option<T>> {
+        let mut chan = self.shared.lock().unwrap();
+        if let Some(value) = chan.buffer.pop_front() {
+            chan.wake_senders();
+            Poll::Ready(Some(value))
+        } else if chan.senders == 0 {
+            Poll::Ready(None)
+        } else {
+            match &mut chan.receiver_waker {
+                Some(waker) => waker.clone_from(cx.waker()),
+                None => chan.receiver_waker = Some(cx.waker().clone()),
+            }
+            Poll::Pending
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        let mut chan = self.shared.lock().unwrap();
+        chan.receiver_gone = true;
+        // waiting senders will never get room now, so let them give up.
+        chan.wake_senders();
+    }
+}
@@ -0,0 +1,130 @@
+//! Wake a waiting task without sending it anything.
+//!
+//! [`Notify::notify_one`] wakes the task which has waited longest in
+//! [`Notify::notified`]. If nobody is waiting, the notification isn't lost:
+//! it is stored as a permit, and the next call to `notified` completes
+//! straight away. At most one permit is stored, however many notifications
+//! arrive.
+//!
+//! ```
+//! use async_intuition::basics::{block_on, executor::Executor, notify::Notify};
+//!
+//! // notified before anyone is waiting.
+//! let notify = Notify::new();
+//! notify.notify_one();
+//! block_on(notify.notified());
+//!
+//! // waiting before anyone notifies.
+//! let notify = Notify::new();
+//! let mut executor = Executor::new();
+//! executor.spawn(notify.notified());
+//! executor.spawn(async { notify.notify_one() });
+//! executor.run();
+//! ```
+
+use std::{
+    collections::{HashSet, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+#[derive(Default)]
+pub struct Notify {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    permit: bool,
+    // waiters which have been notified but not yet polled to find out.
+    notified: HashSet<u64>,
+    waiters: VecDeque<(u64, Waker)>,
+    next_id: u64,
+}
+
+impl State {
+    fn notify_one(&mut self) {
+        match self.waiters.pop_front() {
+            Some((id, waker)) => {
+                self.notified.insert(id);
+                waker.wake();
+            }
+            None => self.permit = true,
+        }
+    }
+}
+
+struct Notified<'a> {
+    notify: &'a Notify,
+    // set once this waiter is queued up.
+    id: Option<u64>,
+}
+
+impl Notify {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn notified(&self) -> impl Future<Output = ()> + '_ {
+        Notified {
+            notify: self,
+            id: None,
+        }
+    }
+
+    pub fn notify_one(&self) {
+        self.state.lock().unwrap().notify_one();
+    }
+}
+
+impl Future for Notified<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let notify = self.notify;
+        let mut state = notify.state.lock().unwrap();
+        match self.id {
+            Some(id) if state.notified.remove(&id) => {
+                self.id = None;
+                Poll::Ready(())
+            }
+            Some(id) => {
+                let (_, waker) = state
+                    .waiters
+                    .iter_mut()
+                    .find(|(other, _)| *other == id)
+                    .unwrap();
+                waker.clone_from(cx.waker());
+                Poll::Pending
+            }
+            None if state.permit => {
+                state.permit = false;
+                Poll::Ready(())
+            }
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back((id, cx.waker().clone()));
+                self.id = Some(id);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Notified<'_> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut state = self.notify.state.lock().unwrap();
+        if state.notified.remove(&id) {
+            // notified, but nobody is left to care. Pass it on.
+            state.notify_one();
+        } else {
+            state.waiters.retain(|(other, _)| *other != id);
+        }
+    }
+}
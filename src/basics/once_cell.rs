@@ -0,0 +1,185 @@
+//! A cell which is initialized at most once, by an async initializer.
+//!
+//! The first task to call [`OnceCell::get_or_init`] runs its initializer.
+//! Any task which turns up while that is still in flight doesn't run its own:
+//! it leaves its waker with the cell and is woken once the value is there.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! use async_intuition::basics::{executor::Executor, once_cell::OnceCell, yield_now};
+//!
+//! let cell = OnceCell::new();
+//! let inits = Cell::new(0);
+//! let init = || async {
+//!     inits.set(inits.get() + 1);
+//!     // long enough for the other task to arrive mid-initialization.
+//!     for _ in 0..3 {
+//!         yield_now().await;
+//!     }
+//!     42
+//! };
+//!
+//! let mut executor = Executor::new();
+//! for _ in 0..2 {
+//!     executor.spawn(async {
+//!         assert_eq!(*cell.get_or_init(init).await, 42);
+//!     });
+//! }
+//! executor.run();
+//! assert_eq!(inits.get(), 1);
+//! assert_eq!(cell.get(), Some(&42));
+//! ```
+//!
+//! If the initializing task is dropped partway through, the cell goes back to
+//! being empty, and the next caller (or one which was already waiting) gets
+//! to run its initializer instead:
+//!
+//! ```
+//! # use futures_lite::future::pending;
+//! use async_intuition::{
+//!     basics::{block_on, once_cell::OnceCell},
+//!     testing::poll_once,
+//! };
+//!
+//! let cell = OnceCell::new();
+//! let mut abandoned = Box::pin(cell.get_or_init(|| pending::<u8>()));
+//! assert!(poll_once(abandoned.as_mut()).is_pending());
+//! drop(abandoned);
+//! assert_eq!(cell.get(), None);
+//!
+//! assert_eq!(*block_on(cell.get_or_init(|| async { 7 })), 7);
+//! ```
+
+use std::{
+    cell::UnsafeCell,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+pub struct OnceCell<T> {
+    state: Mutex<State>,
+    // only ever written once, by whoever moved the state to `Initializing`,
+    // and only read once the state is `Ready`.
+    value: UnsafeCell<Option<T>>,
+}
+
+// SAFETY: the value is written by one task and then only shared, and never
+// both at once (see `value`).
+unsafe impl<T: Send> Send for OnceCell<T> {}
+unsafe impl<T: Send + Sync> Sync for OnceCell<T> {}
+
+// the value itself lives outside the `Mutex`, so that references to it can
+// outlive the lock.
+#[derive(Default)]
+enum State {
+    #[default]
+    Uninit,
+    Initializing {
+        wakers: Vec<Waker>,
+    },
+    Ready,
+}
+
+impl State {
+    fn wake_all(&mut self) {
+        if let State::Initializing { wakers } = self {
+            for waker in wakers.drain(..) {
+                waker.wake();
+            }
+        }
+    }
+}
+
+impl<T> Default for OnceCell<T> {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+impl<T> OnceCell<T> {
+    pub fn new() -> Self {
+        OnceCell {
+            state: Mutex::default(),
+            value: UnsafeCell::new(None),
+        }
+    }
+
+    pub fn get(&self) -> Option<&T> {
+        match *self.state.lock().unwrap() {
+            // SAFETY: once `Ready`, the value is never written again.
+            State::Ready => unsafe { (*self.value.get()).as_ref() },
+            _ => None,
+        }
+    }
+
+    pub async fn get_or_init<F, Fut>(&self, f: F) -> &T
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future<Output = T>,
+    {
+        if let Claim::Initialized = (Wait { cell: self }).await {
+            return self.get().expect("checked by `Wait`");
+        }
+
+        let guard = InitGuard { cell: self };
+        let value = f().await;
+        // SAFETY: `Claim::Ours` means the state went from `Uninit` to
+        // `Initializing` on our say-so, so nobody else writes or reads the
+        // value until we move it on to `Ready`.
+        unsafe { *self.value.get() = Some(value) };
+        let mut state = self.state.lock().unwrap();
+        state.wake_all();
+        *state = State::Ready;
+        drop(state);
+        core::mem::forget(guard);
+        self.get().expect("just initialized")
+    }
+}
+
+enum Claim {
+    Initialized,
+    // the cell was empty, and is now ours to initialize.
+    Ours,
+}
+
+struct Wait<'a, T> {
+    cell: &'a OnceCell<T>,
+}
+
+impl<T> Future for Wait<'_, T> {
+    type Output = Claim;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.cell.state.lock().unwrap();
+        match &mut *state {
+            State::Ready => Poll::Ready(Claim::Initialized),
+            State::Uninit => {
+                *state = State::Initializing { wakers: Vec::new() };
+                Poll::Ready(Claim::Ours)
+            }
+            State::Initializing { wakers } => {
+                // a re-poll from the same task needn't be queued twice.
+                if !wakers.iter().any(|waker| waker.will_wake(cx.waker())) {
+                    wakers.push(cx.waker().clone());
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
+
+// dropped (rather than forgotten) only if the initializer never finished,
+// in which case the cell is handed back for someone else to initialize.
+struct InitGuard<'a, T> {
+    cell: &'a OnceCell<T>,
+}
+
+impl<T> Drop for InitGuard<'_, T> {
+    fn drop(&mut self) {
+        let mut state = self.cell.state.lock().unwrap();
+        state.wake_all();
+        *state = State::Uninit;
+    }
+}
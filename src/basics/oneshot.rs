@@ -0,0 +1,137 @@
+//! A channel for sending a single value from one task to another.
+//!
+//! The [`Receiver`] is itself the future. When polled before a value has
+//! arrived, it leaves its waker in the shared state for [`Sender::send`] to
+//! wake. If the [`Sender`] is dropped without sending, it wakes the receiver
+//! all the same, so that the receiver can find out nothing is coming.
+//!
+//! ```
+//! # use std::{thread, time::Duration};
+//! use async_intuition::basics::{block_on, oneshot::{channel, Canceled}};
+//!
+//! let (tx, rx) = channel();
+//! thread::spawn(move || {
+//!     thread::sleep(Duration::from_millis(10));
+//!     tx.send(5).unwrap();
+//! });
+//! assert_eq!(block_on(rx), Ok(5));
+//!
+//! // sent before the receiver is ever polled.
+//! let (tx, rx) = channel();
+//! tx.send("early").unwrap();
+//! assert_eq!(block_on(rx), Ok("early"));
+//!
+//! let (tx, rx) = channel::<()>();
+//! thread::spawn(move || drop(tx));
+//! assert_eq!(block_on(rx), Err(Canceled));
+//! ```
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+};
+
+pub fn channel<T>() -> (Sender<T>, Receiver<T>) {
+    let shared = Arc::new(Mutex::new(State {
+        value: None,
+        waker: None,
+        sender_gone: false,
+        receiver_gone: false,
+    }));
+    (
+        Sender {
+            shared: Arc::clone(&shared),
+        },
+        Receiver { shared },
+    )
+}
+
+/// The error a [`Receiver`] resolves to if its [`Sender`] was dropped without
+/// sending anything.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Canceled;
+
+impl fmt::Display for Canceled {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("oneshot sender dropped without sending")
+    }
+}
+
+impl std::error::Error for Canceled {}
+
+pub struct Sender<T> {
+    shared: Arc<Mutex<State<T>>>,
+}
+
+pub struct Receiver<T> {
+    shared: Arc<Mutex<State<T>>>,
+}
+
+impl<T> fmt::Debug for Receiver<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Receiver").finish_non_exhaustive()
+    }
+}
+
+struct State<T> {
+    value: Option<T>,
+    // the receiver's waker from its latest poll, if it has been polled.
+    waker: Option<Waker>,
+    sender_gone: bool,
+    receiver_gone: bool,
+}
+
+impl<T> Sender<T> {
+    /// Send `value` to the receiver, or hand it back if the receiver has
+    /// already been dropped.
+    pub fn send(self, value: T) -> Result<(), T> {
+        let mut state = self.shared.lock().unwrap();
+        if state.receiver_gone {
+            return Err(value);
+        }
+        state.value = Some(value);
+        // dropping `self` (with the lock released) does the waking.
+        Ok(())
+    }
+}
+
+impl<T> Drop for Sender<T> {
+    fn drop(&mut self) {
+        let mut state = self.shared.lock().unwrap();
+        state.sender_gone = true;
+        // wake whether or not a value was sent: either way, the receiver now
+        // has its answer.
+        if let Some(waker) = state.waker.take() {
+            drop(state);
+            waker.wake();
+        }
+    }
+}
+
+impl<T> Drop for Receiver<T> {
+    fn drop(&mut self) {
+        self.shared.lock().unwrap().receiver_gone = true;
+    }
+}
+
+impl<T> Future for Receiver<T> {
+    type Output = Result<T, Canceled>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut state = self.shared.lock().unwrap();
+        if let Some(value) = state.value.take() {
+            Poll::Ready(Ok(value))
+        } else if state.sender_gone {
+            Poll::Ready(Err(Canceled))
+        } else {
+            match &mut state.waker {
+                Some(waker) => waker.clone_from(cx.waker()),
+                None => state.waker = Some(cx.waker().clone()),
+            }
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,189 @@
+//! A token bucket: tasks take a token before doing whatever is being rate
+//! limited, and wait asynchronously when the bucket is empty.
+//!
+//! The bucket starts full, holding up to `capacity` tokens, and gains one
+//! every `period` until it is full again. So bursts of up to `capacity` go
+//! straight through, while anything beyond that is spread out to one per
+//! `period`.
+//!
+//! Waiting tasks queue up as in [`semaphore`](super::semaphore), but only the
+//! one at the front of the queue needs a timer. It sleeps until the next
+//! token is due, and once it has taken that token it wakes whoever is next.
+//!
+//! ```
+//! # use std::{pin::pin, time::{Duration, Instant}};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{basics::rate_limiter::RateLimiter, testing::poll_once};
+//!
+//! // a burst within capacity never waits.
+//! let limiter = RateLimiter::new(3, Duration::from_secs(60));
+//! for _ in 0..3 {
+//!     assert!(poll_once(pin!(limiter.acquire())).is_ready());
+//! }
+//! assert!(poll_once(pin!(limiter.acquire())).is_pending());
+//!
+//! // beyond capacity, each token has to be waited for.
+//! let limiter = RateLimiter::new(1, Duration::from_millis(10));
+//! let start = Instant::now();
+//! block_on(async {
+//!     for _ in 0..5 {
+//!         limiter.acquire().await;
+//!     }
+//! });
+//! assert!(start.elapsed() >= Duration::from_millis(40));
+//! ```
+
+use std::{
+    collections::VecDeque,
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{ready, Context, Poll, Waker},
+    time::{Duration, Instant},
+};
+
+use super::Sleep;
+
+pub struct RateLimiter {
+    capacity: usize,
+    period: Duration,
+    state: Mutex<State>,
+}
+
+struct State {
+    tokens: usize,
+    // when the most recent token was added. The next is due one period on.
+    last_refill: Instant,
+    waiters: VecDeque<(u64, Waker)>,
+    next_id: u64,
+}
+
+struct Acquire<'a> {
+    limiter: &'a RateLimiter,
+    // set once this acquire is queued up.
+    id: Option<u64>,
+    // only ever set while at the front of the queue.
+    sleep: Option<Sleep>,
+}
+
+impl RateLimiter {
+    /// # Panics
+    ///
+    /// If `capacity` is zero, as no token could ever be taken, or if
+    /// `period` is zero, as tokens would have to come due infinitely fast.
+    pub fn new(capacity: usize, period: Duration) -> Self {
+        assert!(capacity > 0, "a rate limiter needs a nonzero capacity");
+        assert!(!period.is_zero(), "a rate limiter needs a nonzero period");
+        RateLimiter {
+            capacity,
+            period,
+            state: Mutex::new(State {
+                tokens: capacity,
+                last_refill: Instant::now(),
+                waiters: VecDeque::new(),
+                next_id: 0,
+            }),
+        }
+    }
+
+    pub fn acquire(&self) -> impl Future<Output = ()> + '_ {
+        Acquire {
+            limiter: self,
+            id: None,
+            sleep: None,
+        }
+    }
+
+    // adds the tokens which have come due since the last refill.
+    fn refill(&self, state: &mut State) {
+        let now = Instant::now();
+        let due = (now - state.last_refill).as_nanos() / self.period.as_nanos();
+        if due == 0 {
+            return;
+        }
+        let due = usize::try_from(due).unwrap_or(usize::MAX);
+        state.tokens = state.tokens.saturating_add(due).min(self.capacity);
+        if state.tokens == self.capacity {
+            // a full bucket doesn't bank time towards tokens it can't hold.
+            state.last_refill = now;
+        } else {
+            // fewer than `capacity` tokens came due, which for any sensible
+            // capacity fits in a `u32`.
+            state.last_refill += self.period * due as u32;
+        }
+    }
+}
+
+impl Future for Acquire<'_> {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let limiter = self.limiter;
+        loop {
+            let mut state = limiter.state.lock().unwrap();
+            limiter.refill(&mut state);
+
+            let id = match self.id {
+                Some(id) => id,
+                // a token only counts if nobody is queued up ahead of us.
+                None if state.tokens > 0 && state.waiters.is_empty() => {
+                    state.tokens -= 1;
+                    return Poll::Ready(());
+                }
+                None => {
+                    let id = state.next_id;
+                    state.next_id += 1;
+                    state.waiters.push_back((id, cx.waker().clone()));
+                    self.id = Some(id);
+                    id
+                }
+            };
+
+            let position = state
+                .waiters
+                .iter()
+                .position(|(other, _)| *other == id)
+                .unwrap();
+            state.waiters[position].1.clone_from(cx.waker());
+            if position != 0 {
+                return Poll::Pending;
+            }
+
+            if state.tokens > 0 {
+                state.tokens -= 1;
+                state.waiters.pop_front();
+                // the next in line takes over waiting on the clock.
+                if let Some((_, next)) = state.waiters.front() {
+                    next.wake_by_ref();
+                }
+                self.id = None;
+                self.sleep = None;
+                return Poll::Ready(());
+            }
+
+            let next_token = state.last_refill + limiter.period;
+            drop(state);
+            let sleep = self.sleep.get_or_insert_with(|| Sleep::at(next_token));
+            ready!(Pin::new(sleep).poll(cx));
+            // the token is due now, so go round again to take it.
+            self.sleep = None;
+        }
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut state = self.limiter.state.lock().unwrap();
+        let was_front = state.waiters.front().is_some_and(|(front, _)| *front == id);
+        state.waiters.retain(|(other, _)| *other != id);
+        // otherwise nobody would be watching the clock for the next token.
+        if was_front {
+            if let Some((_, next)) = state.waiters.front() {
+                next.wake_by_ref();
+            }
+        }
+    }
+}
@@ -0,0 +1,266 @@
+//! A single background thread which fires every timer in the process.
+//!
+//! Timers are kept in a min-heap ordered by deadline. The reactor thread parks
+//! until the earliest deadline, wakes everything which has expired, and parks
+//! again. Registering a timer which is due before anything else in the heap
+//! unparks the thread so it can shorten its nap.
+//!
+//! The thread is only spawned the first time [`reactor`] is called, and then
+//! lives for the rest of the process.
+//!
+//! A [`Sleep`](super::Sleep) doesn't have to use that thread, though: it
+//! registers with anything implementing the [`Reactor`] trait. A
+//! [`VirtualReactor`] only fires its timers when told to, which makes for
+//! timer tests which don't have to wait on the wall clock:
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}, time::{Duration, Instant}};
+//! use async_intuition::basics::{reactor::VirtualReactor, Sleep};
+//!
+//! let reactor = VirtualReactor::new();
+//! let deadline = Instant::now() + Duration::from_secs(60 * 60);
+//! let mut sleep = pin!(Sleep::with_reactor(deadline, &reactor));
+//! let mut cx = Context::from_waker(Waker::noop());
+//!
+//! assert_eq!(sleep.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(reactor.pending_timers(), 1);
+//!
+//! // nothing is due yet.
+//! reactor.fire_until(deadline - Duration::from_secs(1));
+//! assert_eq!(sleep.as_mut().poll(&mut cx), Poll::Pending);
+//!
+//! reactor.fire_until(deadline);
+//! assert_eq!(sleep.as_mut().poll(&mut cx), Poll::Ready(()));
+//! assert_eq!(reactor.pending_timers(), 0);
+//! ```
+
+use std::{
+    cmp::Ordering,
+    collections::BinaryHeap,
+    sync::{Arc, Mutex, OnceLock},
+    task::Waker,
+    thread::{self, Thread},
+    time::Instant,
+};
+
+/// Identifies a registered timer to the [`Reactor`] which handed it out.
+/// Reactors other than the ones here can number their timers however they
+/// like.
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord, Hash)]
+pub struct TimerId(pub u64);
+
+/// Anything which can wake a waker once a deadline has passed.
+pub trait Reactor {
+    /// Arrange for `waker` to be woken once `deadline` has passed.
+    fn register(&self, deadline: Instant, waker: Waker) -> TimerId;
+
+    /// Stop tracking a timer, without waking it. Does nothing if the timer
+    /// has already fired.
+    fn deregister(&self, id: TimerId);
+
+    /// The time deadlines are measured against. Only a reactor with a clock
+    /// of its own, like [`MockClock`](crate::testing::MockClock), needs to
+    /// override this.
+    fn now(&self) -> Instant {
+        Instant::now()
+    }
+}
+
+impl<R: Reactor + ?Sized> Reactor for &R {
+    fn register(&self, deadline: Instant, waker: Waker) -> TimerId {
+        (**self).register(deadline, waker)
+    }
+
+    fn deregister(&self, id: TimerId) {
+        (**self).deregister(id)
+    }
+
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+impl<R: Reactor + ?Sized> Reactor for Arc<R> {
+    fn register(&self, deadline: Instant, waker: Waker) -> TimerId {
+        (**self).register(deadline, waker)
+    }
+
+    fn deregister(&self, id: TimerId) {
+        (**self).deregister(id)
+    }
+
+    fn now(&self) -> Instant {
+        (**self).now()
+    }
+}
+
+/// The reactor behind [`reactor`], with a thread of its own.
+pub struct ThreadReactor {
+    timers: Mutex<Timers>,
+    thread: Thread,
+}
+
+#[derive(Default)]
+struct Timers {
+    heap: BinaryHeap<Entry>,
+    next_id: u64,
+}
+
+struct Entry {
+    deadline: Instant,
+    id: TimerId,
+    waker: Waker,
+}
+
+// `BinaryHeap` is a max-heap, so entries compare in reverse to put the
+// earliest deadline on top. Ties are broken by id, which keeps timers with
+// the same deadline firing in registration order.
+impl Ord for Entry {
+    fn cmp(&self, other: &Self) -> Ordering {
+        (other.deadline, other.id).cmp(&(self.deadline, self.id))
+    }
+}
+
+impl PartialOrd for Entry {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl PartialEq for Entry {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other).is_eq()
+    }
+}
+
+impl Eq for Entry {}
+
+impl Timers {
+    // returns whether the new timer is now the earliest.
+    fn insert(&mut self, deadline: Instant, waker: Waker) -> (TimerId, bool) {
+        let id = TimerId(self.next_id);
+        self.next_id += 1;
+
+        let is_earliest = self.heap.peek().is_none_or(|next| deadline < next.deadline);
+        self.heap.push(Entry {
+            deadline,
+            id,
+            waker,
+        });
+        (id, is_earliest)
+    }
+
+    fn remove(&mut self, id: TimerId) {
+        self.heap.retain(|entry| entry.id != id);
+    }
+
+    // moves the wakers of every timer due by `now` into `expired`, and
+    // returns the deadline of the next one after that.
+    fn expire(&mut self, now: Instant, expired: &mut Vec<Waker>) -> Option<Instant> {
+        while self.heap.peek().is_some_and(|next| next.deadline <= now) {
+            expired.push(self.heap.pop().unwrap().waker);
+        }
+        self.heap.peek().map(|next| next.deadline)
+    }
+}
+
+pub fn reactor() -> &'static ThreadReactor {
+    static REACTOR: OnceLock<ThreadReactor> = OnceLock::new();
+    REACTOR.get_or_init(|| {
+        // the new thread calls `reactor()` itself, which blocks until this
+        // initializer has returned.
+        let handle = thread::Builder::new()
+            .name("async-intuition-reactor".into())
+            .spawn(|| reactor().run())
+            .expect("failed to spawn the reactor thread");
+        ThreadReactor {
+            timers: Mutex::new(Timers::default()),
+            thread: handle.thread().clone(),
+        }
+    })
+}
+
+impl Reactor for ThreadReactor {
+    fn register(&self, deadline: Instant, waker: Waker) -> TimerId {
+        let (id, is_earliest) = self.timers.lock().unwrap().insert(deadline, waker);
+        if is_earliest {
+            // if the reactor isn't parked yet, the unpark token makes its
+            // next `park` return immediately, so this can't be missed.
+            self.thread.unpark();
+        }
+        id
+    }
+
+    fn deregister(&self, id: TimerId) {
+        self.timers.lock().unwrap().remove(id);
+    }
+}
+
+impl ThreadReactor {
+    /// The number of timers which are registered but haven't fired yet.
+    pub fn pending_timers(&self) -> usize {
+        self.timers.lock().unwrap().heap.len()
+    }
+
+    fn run(&self) -> ! {
+        let mut expired = Vec::new();
+        loop {
+            let next_deadline = self
+                .timers
+                .lock()
+                .unwrap()
+                .expire(Instant::now(), &mut expired);
+
+            // wake outside the lock: a woken task polled on another thread
+            // may want to register a new timer straight away.
+            for waker in expired.drain(..) {
+                waker.wake();
+            }
+
+            match next_deadline {
+                Some(deadline) => {
+                    thread::park_timeout(deadline.saturating_duration_since(Instant::now()))
+                }
+                None => thread::park(),
+            }
+        }
+    }
+}
+
+/// A reactor with no thread and no clock of its own: its timers only fire
+/// when [`fire_until`](VirtualReactor::fire_until) says time has moved on.
+#[derive(Default)]
+pub struct VirtualReactor {
+    timers: Mutex<Timers>,
+}
+
+impl VirtualReactor {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    /// Fire every timer whose deadline is no later than `now`, in deadline
+    /// order, on the calling thread.
+    pub fn fire_until(&self, now: Instant) {
+        let mut expired = Vec::new();
+        self.timers.lock().unwrap().expire(now, &mut expired);
+        for waker in expired {
+            waker.wake();
+        }
+    }
+
+    /// The number of timers which are registered but haven't fired yet.
+    pub fn pending_timers(&self) -> usize {
+        self.timers.lock().unwrap().heap.len()
+    }
+}
+
+impl Reactor for VirtualReactor {
+    fn register(&self, deadline: Instant, waker: Waker) -> TimerId {
+        self.timers.lock().unwrap().insert(deadline, waker).0
+    }
+
+    fn deregister(&self, id: TimerId) {
+        self.timers.lock().unwrap().remove(id);
+    }
+}
@@ -0,0 +1,71 @@
+//! The two simplest leaf futures there are: one which is done as soon as it is
+//! polled, and one which never will be.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::basics::{pending, ready};
+//!
+//! assert_eq!(block_on(ready(5)), 5);
+//!
+//! let mut never = pin!(pending::<u32>());
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert_eq!(never.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(never.as_mut().poll(&mut cx), Poll::Pending);
+//! ```
+
+use core::{
+    fmt,
+    future::Future,
+    marker::PhantomData,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pub fn ready<T>(value: T) -> Ready<T> {
+    Ready(Some(value))
+}
+
+pub fn pending<T>() -> Pending<T> {
+    Pending(PhantomData)
+}
+
+pub struct Ready<T>(Option<T>);
+
+impl<T> fmt::Debug for Ready<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Ready")
+            .field("taken", &self.0.is_none())
+            .finish_non_exhaustive()
+    }
+}
+
+// the value is only ever moved out, never pinned, so `Ready` can be moved
+// freely whether or not `T` can.
+impl<T> Unpin for Ready<T> {}
+
+impl<T> Future for Ready<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let value = self.0.take().expect("`Ready` polled after completion");
+        Poll::Ready(value)
+    }
+}
+
+pub struct Pending<T>(PhantomData<fn() -> T>);
+
+impl<T> fmt::Debug for Pending<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("Pending")
+    }
+}
+
+impl<T> Future for Pending<T> {
+    type Output = T;
+
+    fn poll(self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Self::Output> {
+        // no waker is stored, because there is nothing to ever wake for.
+        Poll::Pending
+    }
+}
@@ -0,0 +1,103 @@
+//! Spawn futures which borrow from the caller's stack, the way
+//! [`std::thread::scope`] does for threads.
+//!
+//! A spawned task normally can't borrow local state: nothing stops the
+//! caller from returning while the task still holds the borrow. [`scope`]
+//! makes that impossible by not returning until every task spawned through
+//! its [`Scope`] has finished. It runs the body and all the children on an
+//! [`Executor`] of its own, and blocks the thread until that executor runs
+//! dry. If the body or a detached child panics, every child still
+//! outstanding is dropped (so cancelled) along with the executor, before the
+//! panic carries on out of `scope`. Either way, no child outlives the call.
+//!
+//! ```
+//! # use std::{cell::Cell, time::Duration};
+//! use async_intuition::basics::{scope::scope, sleep};
+//!
+//! let counter = Cell::new(0);
+//! let counter = &counter;
+//! let out = scope(|s| async move {
+//!     for millis in [30, 10, 20] {
+//!         s.spawn(async move {
+//!             sleep(Duration::from_millis(millis)).await;
+//!             counter.set(counter.get() + 1);
+//!         });
+//!     }
+//!     // the body can finish first: `scope` still waits for the children.
+//!     "body done"
+//! });
+//! assert_eq!(out, "body done");
+//! assert_eq!(counter.get(), 3);
+//! ```
+//!
+//! Children are cancelled rather than left running when the body bails out
+//! early:
+//!
+//! ```
+//! # use std::{cell::Cell, panic::{self, AssertUnwindSafe}, time::Duration};
+//! use async_intuition::basics::{scope::scope, sleep, yield_now};
+//!
+//! let finished = Cell::new(false);
+//! let finished = &finished;
+//! let result = panic::catch_unwind(AssertUnwindSafe(|| {
+//!     scope(|s| async move {
+//!         s.spawn(async move {
+//!             sleep(Duration::from_millis(10)).await;
+//!             finished.set(true);
+//!         });
+//!         yield_now().await;
+//!         panic!("bailing out");
+//!     })
+//! }));
+//! assert!(result.is_err());
+//! assert!(!finished.get());
+//! ```
+//!
+//! [`Executor`]: super::executor::Executor
+
+use std::{cell::Cell, fmt, future::Future, rc::Rc};
+
+use super::executor::{Executor, JoinHandle, Spawner};
+
+/// Run the future returned by `f` to completion, along with every child
+/// spawned through the [`Scope`] handed to `f`, and return the body's
+/// output.
+pub fn scope<'env, F, Fut>(f: F) -> Fut::Output
+where
+    F: FnOnce(Scope<'env>) -> Fut,
+    Fut: Future + 'env,
+    Fut::Output: 'env,
+{
+    let output = Rc::new(Cell::new(None));
+    let mut executor = Executor::new();
+    let body = f(Scope {
+        spawner: executor.spawner(),
+    });
+    // detached, so a panic in the body unwinds out of `run` and takes the
+    // executor, and every child on it, down with it.
+    let body_output = Rc::clone(&output);
+    executor.spawn(async move { body_output.set(Some(body.await)) });
+    executor.run();
+    output
+        .take()
+        .expect("`run` only returns once every task has finished")
+}
+
+/// Spawns children onto the executor behind a [`scope`]. Anything the
+/// children borrow only has to outlive `'env`, i.e. the call to `scope`.
+#[derive(Clone)]
+pub struct Scope<'env> {
+    spawner: Spawner<'env>,
+}
+
+impl<'env> Scope<'env> {
+    pub fn spawn<T: 'env>(&self, fut: impl Future<Output = T> + 'env) -> JoinHandle<T> {
+        self.spawner.spawn(fut)
+    }
+}
+
+impl fmt::Debug for Scope<'_> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Scope").finish_non_exhaustive()
+    }
+}
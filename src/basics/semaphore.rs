@@ -0,0 +1,153 @@
+//! A semaphore: a pool of permits, where tasks wait asynchronously when none
+//! are left.
+//!
+//! This works just like [`async_mutex`](super::async_mutex), with a count in
+//! place of the `locked` flag. A permit released while nobody is waiting goes
+//! back into the count. A permit released while tasks are waiting goes
+//! straight to the one which has waited longest.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use std::time::Duration;
+//! use async_intuition::basics::{executor::Executor, semaphore::Semaphore, sleep};
+//!
+//! let semaphore = Semaphore::new(2);
+//! let (in_flight, max_in_flight) = (Cell::new(0), Cell::new(0));
+//! let mut executor = Executor::new();
+//! for _ in 0..4 {
+//!     executor.spawn(async {
+//!         let _permit = semaphore.acquire().await;
+//!         in_flight.set(in_flight.get() + 1);
+//!         max_in_flight.set(max_in_flight.get().max(in_flight.get()));
+//!         sleep(Duration::from_millis(10)).await;
+//!         in_flight.set(in_flight.get() - 1);
+//!     });
+//! }
+//! executor.run();
+//! assert_eq!(max_in_flight.get(), 2);
+//! assert_eq!(semaphore.available_permits(), 2);
+//! ```
+
+use std::{
+    collections::{HashSet, VecDeque},
+    future::Future,
+    pin::Pin,
+    sync::Mutex,
+    task::{Context, Poll, Waker},
+};
+
+pub struct Semaphore {
+    state: Mutex<State>,
+}
+
+#[derive(Default)]
+struct State {
+    permits: usize,
+    // waiters which have been handed a permit but not yet polled to collect
+    // it.
+    handed_to: HashSet<u64>,
+    waiters: VecDeque<(u64, Waker)>,
+    next_id: u64,
+}
+
+impl State {
+    fn release(&mut self) {
+        match self.waiters.pop_front() {
+            Some((id, waker)) => {
+                self.handed_to.insert(id);
+                waker.wake();
+            }
+            None => self.permits += 1,
+        }
+    }
+}
+
+pub struct Permit<'a> {
+    semaphore: &'a Semaphore,
+}
+
+struct Acquire<'a> {
+    semaphore: &'a Semaphore,
+    // set once this acquire is queued up.
+    id: Option<u64>,
+}
+
+impl Semaphore {
+    pub fn new(permits: usize) -> Self {
+        Semaphore {
+            state: Mutex::new(State {
+                permits,
+                ..State::default()
+            }),
+        }
+    }
+
+    pub fn acquire(&self) -> impl Future<Output = Permit<'_>> {
+        Acquire {
+            semaphore: self,
+            id: None,
+        }
+    }
+
+    /// The number of permits nobody holds or has been handed.
+    pub fn available_permits(&self) -> usize {
+        self.state.lock().unwrap().permits
+    }
+}
+
+impl<'a> Future for Acquire<'a> {
+    type Output = Permit<'a>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let semaphore = self.semaphore;
+        let mut state = semaphore.state.lock().unwrap();
+        match self.id {
+            Some(id) if state.handed_to.remove(&id) => {
+                self.id = None;
+                Poll::Ready(Permit { semaphore })
+            }
+            Some(id) => {
+                let (_, waker) = state
+                    .waiters
+                    .iter_mut()
+                    .find(|(other, _)| *other == id)
+                    .unwrap();
+                waker.clone_from(cx.waker());
+                Poll::Pending
+            }
+            // a free permit only counts if nobody is queued up ahead of us.
+            None if state.permits > 0 && state.waiters.is_empty() => {
+                state.permits -= 1;
+                Poll::Ready(Permit { semaphore })
+            }
+            None => {
+                let id = state.next_id;
+                state.next_id += 1;
+                state.waiters.push_back((id, cx.waker().clone()));
+                self.id = Some(id);
+                Poll::Pending
+            }
+        }
+    }
+}
+
+impl Drop for Acquire<'_> {
+    fn drop(&mut self) {
+        let Some(id) = self.id else {
+            return;
+        };
+        let mut state = self.semaphore.state.lock().unwrap();
+        if state.handed_to.remove(&id) {
+            // handed a permit, but nobody is left to use it.
+            state.release();
+        } else {
+            state.waiters.retain(|(other, _)| *other != id);
+        }
+    }
+}
+
+impl Drop for Permit<'_> {
+    fn drop(&mut self) {
+        self.semaphore.state.lock().unwrap().release();
+    }
+}
@@ -0,0 +1,367 @@
+//! A future which completes once a duration has passed.
+//!
+//! Something has to call [`Waker::wake`] when the time is up, and the future
+//! itself only runs while it is being polled. Here that something is the
+//! shared [reactor](super::reactor) thread: on first poll, a [`Sleep`]
+//! registers its deadline there along with a waker of its own, which marks the
+//! sleep as done before passing the wakeup on to the task.
+//!
+//! ```
+//! # use std::{pin::pin, task::Poll, time::Duration};
+//! use async_intuition::testing::{poll_once, MockClock};
+//!
+//! // the same as `sleep`, but by a clock which only moves when told to.
+//! let clock = MockClock::new();
+//! let mut sleep = pin!(clock.sleep(Duration::from_millis(50)));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Pending);
+//!
+//! clock.advance(Duration::from_millis(49));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Pending);
+//! clock.advance(Duration::from_millis(1));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Ready(()));
+//! ```
+//!
+//! However many sleeps are in flight, they all share the one reactor thread:
+//!
+//! ```
+//! # use std::{
+//! #     collections::HashSet,
+//! #     future::Future,
+//! #     sync::{Arc, Mutex},
+//! #     task::{Context, Wake, Waker},
+//! #     thread::{self, ThreadId},
+//! #     time::Duration,
+//! # };
+//! use async_intuition::basics::sleep;
+//!
+//! #[derive(Default)]
+//! struct WakingThreads(Mutex<HashSet<ThreadId>>);
+//!
+//! impl Wake for WakingThreads {
+//!     fn wake(self: Arc<Self>) {
+//!         self.0.lock().unwrap().insert(thread::current().id());
+//!     }
+//! }
+//!
+//! let waking_threads = Arc::new(WakingThreads::default());
+//! let waker = Waker::from(Arc::clone(&waking_threads));
+//! let mut cx = Context::from_waker(&waker);
+//!
+//! let mut sleeps: Vec<_> = (0..100)
+//!     .map(|i| Box::pin(sleep(Duration::from_millis(i % 10))))
+//!     .collect();
+//! while !sleeps.is_empty() {
+//!     sleeps.retain_mut(|sleep| sleep.as_mut().poll(&mut cx).is_pending());
+//!     thread::sleep(Duration::from_millis(1));
+//! }
+//!
+//! let waking_threads = waking_threads.0.lock().unwrap();
+//! assert_eq!(waking_threads.len(), 1);
+//! assert!(!waking_threads.contains(&thread::current().id()));
+//! ```
+//!
+//! Dropping a [`Sleep`] before it fires takes its timer back out of the
+//! reactor, so abandoned sleeps don't pile up:
+//!
+//! ```
+//! # use std::{future::Future, task::{Context, Waker}, time::Duration};
+//! use async_intuition::basics::{reactor::reactor, sleep};
+//!
+//! let mut long = Box::pin(sleep(Duration::from_secs(60)));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert!(long.as_mut().poll(&mut cx).is_pending());
+//! assert_eq!(reactor().pending_timers(), 1);
+//!
+//! drop(long);
+//! assert_eq!(reactor().pending_timers(), 0);
+//! ```
+//!
+//! [`Sleep::at`] sleeps until an absolute deadline instead, completing on the
+//! very first poll if that deadline has already passed:
+//!
+//! ```
+//! # use std::{pin::pin, task::Poll, time::{Duration, Instant}};
+//! use async_intuition::{basics::Sleep, testing::{poll_once, MockClock}};
+//!
+//! let past = Instant::now();
+//! assert_eq!(poll_once(pin!(Sleep::at(past))), Poll::Ready(()));
+//!
+//! let clock = MockClock::new();
+//! let future = clock.now() + Duration::from_millis(20);
+//! let mut sleep = pin!(Sleep::with_reactor(future, &clock));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Pending);
+//! clock.advance(Duration::from_millis(20));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Ready(()));
+//! ```
+//!
+//! A sleep can also be rescheduled while in flight with [`Sleep::reset`]:
+//!
+//! ```
+//! # use std::{pin::pin, task::Poll, time::Duration};
+//! use async_intuition::testing::{poll_once, MockClock};
+//!
+//! let clock = MockClock::new();
+//! let mut sleep = pin!(clock.sleep(Duration::from_millis(50)));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Pending);
+//!
+//! sleep.as_mut().reset(Duration::from_millis(10));
+//! clock.advance(Duration::from_millis(10));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Ready(()));
+//! ```
+//!
+//! Polling again from the same task keeps the waker stored on first poll
+//! rather than cloning it again (see
+//! [`shared_state`](crate::two_ways::shared_state)):
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::Context, time::Duration};
+//! use async_intuition::{basics::sleep, testing::RecordingWaker};
+//!
+//! let recorder = RecordingWaker::new();
+//! let waker = recorder.waker();
+//! let mut cx = Context::from_waker(&waker);
+//!
+//! let mut sleep = pin!(sleep(Duration::from_secs(60)));
+//! for _ in 0..5 {
+//!     assert!(sleep.as_mut().poll(&mut cx).is_pending());
+//! }
+//! assert_eq!(recorder.clones(), 1);
+//! ```
+//!
+//! [`Sleep::with_reactor`] registers with some other [`Reactor`] instead,
+//! such as a [`VirtualReactor`](super::reactor::VirtualReactor) for tests.
+//!
+//! [`Waker::wake`]: std::task::Waker::wake
+
+use std::{
+    fmt,
+    future::Future,
+    pin::Pin,
+    sync::Arc,
+    task::{Context, Poll, Wake, Waker},
+    time::{Duration, Instant},
+};
+
+use super::{
+    reactor::{reactor, Reactor, ThreadReactor, TimerId},
+    sync::Mutex,
+};
+
+pub fn sleep(duration: Duration) -> Sleep {
+    Sleep::at(Instant::now() + duration)
+}
+
+pub struct Sleep<R: Reactor = &'static ThreadReactor> {
+    deadline: Instant,
+    reactor: R,
+    // `None` until first poll, at which point the timer is registered.
+    timer: Option<Arc<Timer>>,
+    // `None` before first poll and once the timer has fired, i.e. whenever
+    // the reactor isn't tracking this sleep.
+    timer_id: Option<TimerId>,
+}
+
+// shows where the sleep is up to, rather than the reactor and timer.
+impl<R: Reactor> fmt::Debug for Sleep<R> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Sleep")
+            .field("deadline", &self.deadline)
+            .field("registered", &self.timer_id.is_some())
+            .finish_non_exhaustive()
+    }
+}
+
+// nothing is structurally pinned: the timer lives behind an `Arc`.
+impl<R: Reactor> Unpin for Sleep<R> {}
+
+impl Sleep {
+    /// Sleep until `deadline` rather than for a duration. Several sleeps
+    /// created from the same deadline all fire together, however long apart
+    /// they were created.
+    pub fn at(deadline: Instant) -> Sleep {
+        Sleep::with_reactor(deadline, reactor())
+    }
+}
+
+impl<R: Reactor> Sleep<R> {
+    /// Sleep until `deadline`, with the timer registered on `reactor` instead
+    /// of the shared reactor thread.
+    pub fn with_reactor(deadline: Instant, reactor: R) -> Sleep<R> {
+        Sleep {
+            deadline,
+            reactor,
+            timer: None,
+            timer_id: None,
+        }
+    }
+
+    /// Reschedule the sleep to complete `new_duration` from now, whether or
+    /// not it has already completed.
+    pub fn reset(self: Pin<&mut Self>, new_duration: Duration) {
+        let this = self.get_mut();
+        this.deadline = this.reactor.now() + new_duration;
+        // not polled yet: the timer will pick up the new deadline once it is.
+        let Some(old) = &this.timer else {
+            return;
+        };
+
+        if let Some(id) = this.timer_id.take() {
+            this.reactor.deregister(id);
+        }
+        // the old timer may be firing on the reactor thread right now, so
+        // rather than risk that wakeup marking the new deadline as done,
+        // register a fresh timer and let the old one go stale.
+        let waker = old.shared.lock().unwrap().waker.clone();
+        let (timer, id) = Timer::register(&this.reactor, this.deadline, waker);
+        this.timer = Some(timer);
+        this.timer_id = Some(id);
+    }
+}
+
+// the waker handed to the reactor. Waking it records that the deadline has
+// passed, then forwards the wakeup to whichever task last polled the `Sleep`.
+struct Timer {
+    shared: Mutex<Shared>,
+}
+
+struct Shared {
+    waker: Waker,
+    done: bool,
+}
+
+impl Timer {
+    fn new(waker: Waker) -> Arc<Self> {
+        Arc::new(Timer {
+            shared: Mutex::new(Shared { waker, done: false }),
+        })
+    }
+
+    fn register(reactor: &impl Reactor, deadline: Instant, waker: Waker) -> (Arc<Self>, TimerId) {
+        let timer = Timer::new(waker);
+        let id = reactor.register(deadline, Waker::from(Arc::clone(&timer)));
+        (timer, id)
+    }
+
+    // the poller's half of the handshake with `wake_by_ref`. Checking `done`
+    // and swapping in the new waker under the same lock means a wakeup either
+    // happened before (and we see `done`) or happens after (and reaches the
+    // new waker), never in between.
+    fn poll(&self, waker: &Waker) -> Poll<()> {
+        let mut shared = self.shared.lock().unwrap();
+        if shared.done {
+            Poll::Ready(())
+        } else {
+            // the task may have moved to a different executor (or a different
+            // task on the same one) since we were last polled. Usually it
+            // hasn't, and keeping the waker we have saves a clone.
+            if !shared.waker.will_wake(waker) {
+                shared.waker.clone_from(waker);
+            }
+            Poll::Pending
+        }
+    }
+}
+
+impl Wake for Timer {
+    fn wake(self: Arc<Self>) {
+        self.wake_by_ref();
+    }
+
+    fn wake_by_ref(self: &Arc<Self>) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.done = true;
+        let waker = shared.waker.clone();
+        // a waker which polls the task right here would call `Timer::poll`,
+        // which needs this lock.
+        drop(shared);
+        waker.wake();
+    }
+}
+
+impl<R: Reactor> Future for Sleep<R> {
+    type Output = ();
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let this = self.get_mut();
+        if this.timer.is_none() && this.deadline <= this.reactor.now() {
+            // no need to bother the reactor with a deadline which has
+            // already passed.
+            return Poll::Ready(());
+        }
+
+        let timer = this.timer.get_or_insert_with(|| {
+            let (timer, id) = Timer::register(&this.reactor, this.deadline, cx.waker().clone());
+            this.timer_id = Some(id);
+            timer
+        });
+
+        let poll = timer.poll(cx.waker());
+        if poll.is_ready() {
+            this.timer_id = None;
+        }
+        poll
+    }
+}
+
+impl<R: Reactor> Drop for Sleep<R> {
+    fn drop(&mut self) {
+        if let Some(id) = self.timer_id {
+            self.reactor.deregister(id);
+        }
+    }
+}
+
+#[cfg(all(test, feature = "loom"))]
+mod loom_tests {
+    use std::{
+        sync::Arc,
+        task::{Poll, Wake, Waker},
+    };
+
+    use loom::{
+        sync::atomic::{AtomicUsize, Ordering},
+        thread,
+    };
+
+    use super::Timer;
+
+    #[derive(Default)]
+    struct CountWakes(AtomicUsize);
+
+    impl Wake for CountWakes {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            self.0.fetch_add(1, Ordering::SeqCst);
+        }
+    }
+
+    #[test]
+    fn wakeup_reaches_the_latest_waker() {
+        loom::model(|| {
+            let first = Arc::new(CountWakes::default());
+            let timer = Timer::new(Waker::from(Arc::clone(&first)));
+
+            let reactor_side = Waker::from(Arc::clone(&timer));
+            let fire = thread::spawn(move || reactor_side.wake());
+
+            let second = Arc::new(CountWakes::default());
+            let polled = timer.poll(&Waker::from(Arc::clone(&second)));
+            fire.join().unwrap();
+
+            let wakes = (
+                first.0.load(Ordering::SeqCst),
+                second.0.load(Ordering::SeqCst),
+            );
+            if polled.is_pending() {
+                // the wakeup must reach the waker registered by this poll.
+                assert_eq!(wakes, (0, 1));
+            } else {
+                assert_eq!(wakes, (1, 0));
+            }
+            assert_eq!(timer.poll(Waker::noop()), Poll::Ready(()));
+        });
+    }
+}
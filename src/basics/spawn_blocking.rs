@@ -0,0 +1,90 @@
+//! Run blocking work on a thread of its own, and await its result.
+//!
+//! A future which spends a long time inside `poll` holds up every other task
+//! on its executor, and a future can't be woken halfway through a blocking
+//! call. Instead, [`spawn_blocking`] hands the closure to a fresh thread and
+//! returns a [`Blocking`] future which only has to wait for the result to turn
+//! up, the same way a [`oneshot`](super::oneshot) receiver does.
+//!
+//! A panic in the closure doesn't take the awaiting task down with it: it
+//! comes back as the `Err` side of a [`thread::Result`], just like
+//! [`JoinHandle::join`](std::thread::JoinHandle::join).
+//!
+//! ```
+//! use async_intuition::basics::{block_on, spawn_blocking::spawn_blocking};
+//!
+//! let sum = block_on(spawn_blocking(|| (1..=1_000_000u64).sum::<u64>()));
+//! assert_eq!(sum.unwrap(), 500_000_500_000);
+//!
+//! let panicked = block_on(spawn_blocking(|| -> u32 { panic!("oh no") }));
+//! assert_eq!(*panicked.unwrap_err().downcast::<&str>().unwrap(), "oh no");
+//! ```
+
+use std::{
+    fmt,
+    future::Future,
+    panic::{self, AssertUnwindSafe},
+    pin::Pin,
+    sync::{Arc, Mutex},
+    task::{Context, Poll, Waker},
+    thread,
+};
+
+pub fn spawn_blocking<F, T>(f: F) -> Blocking<T>
+where
+    F: FnOnce() -> T + Send + 'static,
+    T: Send + 'static,
+{
+    let shared = Arc::new(Mutex::new(Slot {
+        result: None,
+        waker: None,
+    }));
+    let thread_shared = Arc::clone(&shared);
+    thread::spawn(move || {
+        // `f` is never touched again after a panic, so it can't be observed
+        // in a broken state.
+        let result = panic::catch_unwind(AssertUnwindSafe(f));
+        let mut slot = thread_shared.lock().unwrap();
+        slot.result = Some(result);
+        if let Some(waker) = slot.waker.take() {
+            drop(slot);
+            waker.wake();
+        }
+    });
+    Blocking { shared }
+}
+
+pub struct Blocking<T> {
+    shared: Arc<Mutex<Slot<T>>>,
+}
+
+impl<T> fmt::Debug for Blocking<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Blocking").finish_non_exhaustive()
+    }
+}
+
+struct Slot<T> {
+    // `Some` once the thread has finished, until taken by the `Blocking`.
+    result: Option<thread::Result<T>>,
+    // the awaiting task's waker from its latest poll.
+    waker: Option<Waker>,
+}
+
+impl<T> Future for Blocking<T> {
+    type Output = thread::Result<T>;
+
+    fn poll(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        let mut slot = self.shared.lock().unwrap();
+        match slot.result.take() {
+            Some(result) => Poll::Ready(result),
+            None => {
+                match &mut slot.waker {
+                    Some(waker) => waker.clone_from(cx.waker()),
+                    None => slot.waker = Some(cx.waker().clone()),
+                }
+                Poll::Pending
+            }
+        }
+    }
+}
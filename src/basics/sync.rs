@@ -0,0 +1,13 @@
+//! Locks shared between a future and the thread which wakes it, swapped for
+//! loom's model-checked versions when testing with the `loom` feature.
+//!
+//! Only the lock is swapped. Whatever ends up inside a [`Waker`] has to be a
+//! std [`Arc`](std::sync::Arc), since that is what [`Wake`] is implemented for.
+//!
+//! [`Waker`]: std::task::Waker
+//! [`Wake`]: std::task::Wake
+
+#[cfg(all(test, feature = "loom"))]
+pub(crate) use loom::sync::Mutex;
+#[cfg(not(all(test, feature = "loom")))]
+pub(crate) use std::sync::Mutex;
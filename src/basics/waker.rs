@@ -0,0 +1,88 @@
+//! Building a [`Waker`] from scratch.
+//!
+//! A [`Waker`] is a type-erased pointer plus a [`RawWakerVTable`] of functions
+//! which know what the pointer really is. [`waker_from_arc`] erases an
+//! [`Arc`] of anything implementing [`Wake`], with each vtable function
+//! turning the pointer back into the `Arc` it came from. This is what
+//! `Waker::from(arc)` does in std.
+//!
+//! ```
+//! # use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+//! # use std::task::Wake;
+//! use async_intuition::basics::waker::waker_from_arc;
+//!
+//! #[derive(Default)]
+//! struct CountWakes(AtomicUsize);
+//!
+//! impl Wake for CountWakes {
+//!     fn wake(self: Arc<Self>) {
+//!         self.wake_by_ref();
+//!     }
+//!
+//!     fn wake_by_ref(self: &Arc<Self>) {
+//!         self.0.fetch_add(1, Ordering::Relaxed);
+//!     }
+//! }
+//!
+//! let count = Arc::new(CountWakes::default());
+//! let waker = waker_from_arc(Arc::clone(&count));
+//! assert_eq!(Arc::strong_count(&count), 2);
+//!
+//! let cloned = waker.clone();
+//! assert_eq!(Arc::strong_count(&count), 3);
+//! drop(cloned);
+//! assert_eq!(Arc::strong_count(&count), 2);
+//!
+//! waker.wake_by_ref();
+//! assert_eq!(count.0.load(Ordering::Relaxed), 1);
+//! waker.wake();
+//! assert_eq!(count.0.load(Ordering::Relaxed), 2);
+//! assert_eq!(Arc::strong_count(&count), 1);
+//! ```
+
+use alloc::{sync::Arc, task::Wake};
+use core::task::{RawWaker, RawWakerVTable, Waker};
+
+// a `Waker` may be sent to and woken from any thread, hence `Send + Sync`.
+pub fn waker_from_arc<W: Wake + Send + Sync + 'static>(arc: Arc<W>) -> Waker {
+    let data = Arc::into_raw(arc).cast::<()>();
+    // SAFETY: `data` came from `Arc::<W>::into_raw`, which is exactly what
+    // every function in the vtable expects.
+    unsafe { Waker::from_raw(RawWaker::new(data, arc_vtable::<W>())) }
+}
+
+/// A waker which does nothing when woken, for polling futures by hand.
+pub fn noop_waker() -> Waker {
+    const VTABLE: RawWakerVTable = RawWakerVTable::new(|_| RAW, |_| {}, |_| {}, |_| {});
+    const RAW: RawWaker = RawWaker::new(core::ptr::null(), &VTABLE);
+    // SAFETY: none of the vtable functions touch the (null) data pointer.
+    unsafe { Waker::from_raw(RAW) }
+}
+
+// one vtable per `W`, since each function has to know which `Arc` to rebuild.
+fn arc_vtable<W: Wake + Send + Sync + 'static>() -> &'static RawWakerVTable {
+    &RawWakerVTable::new(clone::<W>, wake::<W>, wake_by_ref::<W>, drop::<W>)
+}
+
+// each function below is only ever passed a `data` pointer which came from
+// `Arc::<W>::into_raw` and still owns one strong count.
+
+unsafe fn clone<W: Wake + Send + Sync + 'static>(data: *const ()) -> RawWaker {
+    unsafe { Arc::increment_strong_count(data.cast::<W>()) };
+    RawWaker::new(data, arc_vtable::<W>())
+}
+
+unsafe fn wake<W: Wake + Send + Sync + 'static>(data: *const ()) {
+    let arc = unsafe { Arc::from_raw(data.cast::<W>()) };
+    arc.wake();
+}
+
+unsafe fn wake_by_ref<W: Wake + Send + Sync + 'static>(data: *const ()) {
+    // borrow the `Arc` without taking over its strong count.
+    let arc = core::mem::ManuallyDrop::new(unsafe { Arc::from_raw(data.cast::<W>()) });
+    arc.wake_by_ref();
+}
+
+unsafe fn drop<W: Wake + Send + Sync + 'static>(data: *const ()) {
+    unsafe { Arc::decrement_strong_count(data.cast::<W>()) };
+}
@@ -0,0 +1,46 @@
+//! A future which gives other tasks a chance to run before completing.
+//!
+//! Returning `Pending` on its own would leave the task asleep forever, since
+//! nothing else knows to wake it. So before returning `Pending`, [`YieldNow`]
+//! wakes itself: the executor puts the task at the back of its queue, and the
+//! second poll completes. Compare with [`Sleep`](super::Sleep), which hands its
+//! waker to another thread to call later.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! use async_intuition::basics::yield_now;
+//!
+//! let mut fut = pin!(yield_now());
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(()));
+//! ```
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+pub fn yield_now() -> YieldNow {
+    YieldNow { yielded: false }
+}
+
+#[derive(Debug)]
+pub struct YieldNow {
+    yielded: bool,
+}
+
+impl Future for YieldNow {
+    type Output = ();
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        if self.yielded {
+            Poll::Ready(())
+        } else {
+            self.yielded = true;
+            cx.waker().wake_by_ref();
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,63 @@
+//! Concrete, never-inlined instances of [`a_then_b`](crate::two_ways::a_then_b)
+//! whose assembly can be compared, to check the claim that `auto` and `manual`
+//! compile down to essentially the same code.
+//!
+//! Generic functions only turn into machine code once they're used with
+//! concrete types, and then usually get inlined into the caller. Each function
+//! here fixes the leaf futures and keeps its own symbol, so with the
+//! `codegen-demo` feature enabled:
+//!
+//! ```text
+//! cargo asm --features codegen-demo --lib async_intuition::codegen_demo::a_then_b_auto_ready
+//! cargo asm --features codegen-demo --lib async_intuition::codegen_demo::a_then_b_manual_ready
+//! ```
+//!
+//! Each one polls its future to completion with the given context, so the
+//! whole state machine, from creation to the final `Ready`, ends up in the
+//! one function. Both versions move on to `b` within the poll in which `a`
+//! finishes, so they take the same polls and their listings can be compared
+//! state for state.
+
+use core::{
+    future::Future,
+    pin::pin,
+    task::{Context, Poll},
+};
+
+use crate::{
+    basics::{ready, yield_now},
+    two_ways::a_then_b::{auto, manual},
+};
+
+fn poll_to_completion<F: Future>(fut: F, cx: &mut Context<'_>) -> F::Output {
+    let mut fut = pin!(fut);
+    loop {
+        if let Poll::Ready(out) = fut.as_mut().poll(cx) {
+            return out;
+        }
+    }
+}
+
+/// Both halves are ready on their first poll.
+#[inline(never)]
+pub fn a_then_b_auto_ready(cx: &mut Context<'_>) {
+    poll_to_completion(auto::a_then_b(ready(()), ready(())), cx)
+}
+
+/// Both halves are ready on their first poll.
+#[inline(never)]
+pub fn a_then_b_manual_ready(cx: &mut Context<'_>) {
+    poll_to_completion(manual::a_then_b(ready(()), ready(())), cx)
+}
+
+/// Both halves return `Pending` once, so the state is resumed mid-way.
+#[inline(never)]
+pub fn a_then_b_auto_yield_now(cx: &mut Context<'_>) {
+    poll_to_completion(auto::a_then_b(yield_now(), yield_now()), cx)
+}
+
+/// Both halves return `Pending` once, so the state is resumed mid-way.
+#[inline(never)]
+pub fn a_then_b_manual_yield_now(cx: &mut Context<'_>) {
+    poll_to_completion(manual::a_then_b(yield_now(), yield_now()), cx)
+}
@@ -0,0 +1,79 @@
+//! A value which is one of two types, shared by every combinator which needs
+//! one: [`select`](crate::two_ways::select) and
+//! [`select_biased`](crate::two_ways::select_biased) resolve to an [`Either`]
+//! saying which future won, and [`two_ways::either`](crate::two_ways::either)
+//! makes it a future itself whenever both sides are futures.
+//!
+//! ```
+//! use async_intuition::either::Either;
+//!
+//! let left: Either<u8, &str> = Either::Left(2);
+//! let right: Either<u8, &str> = Either::Right("two");
+//!
+//! assert!(left.is_left() && !left.is_right());
+//! assert!(right.is_right() && !right.is_left());
+//! assert_eq!((left.left(), left.right()), (Some(2), None));
+//! assert_eq!((right.left(), right.right()), (None, Some("two")));
+//!
+//! // mapping one side leaves the other alone.
+//! assert_eq!(left.map_left(|n| n * 10), Either::Left(20));
+//! assert_eq!(right.map_left(|n| n * 10), Either::Right("two"));
+//! assert_eq!(right.map_right(str::len), Either::Right(3));
+//! assert_eq!(left.map_right(str::len), Either::Left(2));
+//!
+//! // equal only with the same side and an equal value.
+//! assert_eq!(left, Either::Left(2));
+//! assert_ne!(left, Either::Left(3));
+//! assert_ne!(Either::<u8, u8>::Left(2), Either::Right(2));
+//! ```
+
+use pin_project::pin_project;
+
+// each side is structurally pinned, so that an `Either` of two futures can
+// poll whichever one it holds in place.
+#[pin_project(project = EitherProj)]
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Either<L, R> {
+    Left(#[pin] L),
+    Right(#[pin] R),
+}
+
+impl<L, R> Either<L, R> {
+    pub fn is_left(&self) -> bool {
+        matches!(self, Either::Left(_))
+    }
+
+    pub fn is_right(&self) -> bool {
+        matches!(self, Either::Right(_))
+    }
+
+    /// The left value, or `None` if this is a `Right`.
+    pub fn left(self) -> Option<L> {
+        match self {
+            Either::Left(left) => Some(left),
+            Either::Right(_) => None,
+        }
+    }
+
+    /// The right value, or `None` if this is a `Left`.
+    pub fn right(self) -> Option<R> {
+        match self {
+            Either::Left(_) => None,
+            Either::Right(right) => Some(right),
+        }
+    }
+
+    pub fn map_left<T>(self, f: impl FnOnce(L) -> T) -> Either<T, R> {
+        match self {
+            Either::Left(left) => Either::Left(f(left)),
+            Either::Right(right) => Either::Right(right),
+        }
+    }
+
+    pub fn map_right<T>(self, f: impl FnOnce(R) -> T) -> Either<L, T> {
+        match self {
+            Either::Left(left) => Either::Left(left),
+            Either::Right(right) => Either::Right(f(right)),
+        }
+    }
+}
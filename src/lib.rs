@@ -1,2 +1,12 @@
+#![cfg_attr(not(feature = "std"), no_std)]
+
+extern crate alloc;
+
+pub mod basics;
+#[cfg(feature = "codegen-demo")]
+pub mod codegen_demo;
+pub mod either;
 pub mod pin_and_suffering;
+pub mod streams;
+pub mod testing;
 pub mod two_ways;
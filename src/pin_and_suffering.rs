@@ -82,6 +82,8 @@
 //! 3. the only allowed mutating operation to [`Pin<Ptr>`][`Pin`] is
 //!    [`Pin::set`].
 //!
+//! The compile errors for breaking the first two are checked in `tests/ui`.
+//!
 //! module name stolen from <https://fasterthanli.me/articles/pin-and-suffering>
 //!
 //! [`Future`]: std::future::Future
@@ -0,0 +1,42 @@
+//! Count up from zero, yielding `0..n`. Every item is ready straight away,
+//! so this is the stream equivalent of [`ready`](crate::basics::ready()).
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::streams::{collect, count::manual};
+//!
+//! assert_eq!(block_on(collect(manual::count(5))), [0, 1, 2, 3, 4]);
+//! assert_eq!(block_on(collect(manual::count(0))), []);
+//! ```
+
+pub mod manual {
+    use core::{
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use crate::streams::Stream;
+
+    pub fn count(n: usize) -> Count {
+        Count { next: 0, end: n }
+    }
+
+    #[derive(Debug)]
+    pub struct Count {
+        next: usize,
+        end: usize,
+    }
+
+    impl Stream for Count {
+        type Item = usize;
+
+        fn poll_next(mut self: Pin<&mut Self>, _cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            if self.next == self.end {
+                return Poll::Ready(None);
+            }
+            let item = self.next;
+            self.next += 1;
+            Poll::Ready(Some(item))
+        }
+    }
+}
@@ -0,0 +1,98 @@
+//! Repeat a finite stream forever: the first time through, every item is
+//! passed along and a clone of it kept, and once the stream ends, the kept
+//! items are replayed from the start, over and over.
+//!
+//! A stream can't be rewound the way a `Clone` iterator can be copied before
+//! it starts, so the buffer is what makes restarting possible, and why the
+//! items have to be `Clone`. A source which ends without yielding anything
+//! leaves nothing to replay, so the cycle ends too instead of spinning.
+//!
+//! There is no `auto` version, for want of `async` generators.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::streams::{
+//!     collect, count::manual::count, cycle::manual, map::manual::map, take::manual::take,
+//! };
+//!
+//! let one_two_three = map(count(3), |n| n + 1);
+//! let cycled = take(manual::cycle(one_two_three), 7);
+//! assert_eq!(block_on(collect(cycled)), [1, 2, 3, 1, 2, 3, 1]);
+//!
+//! assert_eq!(block_on(collect(manual::cycle(count(0)))), []);
+//! ```
+
+pub mod manual {
+    use alloc::vec::Vec;
+    use core::{
+        fmt,
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use crate::streams::Stream;
+
+    pub fn cycle<St>(stream: St) -> Cycle<St>
+    where
+        St: Stream,
+        St::Item: Clone,
+    {
+        Cycle {
+            stream,
+            stream_done: false,
+            buffer: Vec::new(),
+            next: 0,
+        }
+    }
+
+    #[pin_project]
+    pub struct Cycle<St: Stream> {
+        #[pin]
+        stream: St,
+        // once set, items come from `buffer` and `stream` is left alone.
+        stream_done: bool,
+        buffer: Vec<St::Item>,
+        // the index in `buffer` of the next item to replay.
+        next: usize,
+    }
+
+    impl<St: Stream> fmt::Debug for Cycle<St> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Cycle")
+                .field("stream_done", &self.stream_done)
+                .field("buffered", &self.buffer.len())
+                .field("next", &self.next)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<St> Stream for Cycle<St>
+    where
+        St: Stream,
+        St::Item: Clone,
+    {
+        type Item = St::Item;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.project();
+            if !*this.stream_done {
+                match ready!(this.stream.poll_next(cx)) {
+                    Some(item) => {
+                        this.buffer.push(item.clone());
+                        return Poll::Ready(Some(item));
+                    }
+                    None => *this.stream_done = true,
+                }
+            }
+
+            if this.buffer.is_empty() {
+                return Poll::Ready(None);
+            }
+            let item = this.buffer[*this.next].clone();
+            *this.next = (*this.next + 1) % this.buffer.len();
+            Poll::Ready(Some(item))
+        }
+    }
+}
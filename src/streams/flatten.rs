@@ -0,0 +1,83 @@
+//! Turn a future which produces a stream into the stream itself: nothing is
+//! yielded until the future has completed, after which every item comes from
+//! the stream it produced.
+//!
+//! The stream counterpart of [`two_ways::flatten`](crate::two_ways::flatten),
+//! with the same two-state enum, except that the second state is polled with
+//! `poll_next` for as long as the stream lasts. There is no `auto` version,
+//! for want of `async` generators.
+//!
+//! ```
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::streams::{collect, count::manual::count, flatten::manual};
+//!
+//! let counter = async {
+//!     yield_now().await;
+//!     count(4)
+//! };
+//! assert_eq!(block_on(collect(manual::flatten_stream(counter))), [0, 1, 2, 3]);
+//! ```
+
+pub mod manual {
+    use core::{
+        fmt,
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use crate::streams::Stream;
+
+    pub fn flatten_stream<Fut>(fut: Fut) -> FlattenStream<Fut, Fut::Output>
+    where
+        Fut: Future,
+        Fut::Output: Stream,
+    {
+        FlattenStream::Future { fut }
+    }
+
+    #[pin_project(project = FlattenStreamProj)]
+    pub enum FlattenStream<Fut, St> {
+        Future {
+            #[pin]
+            fut: Fut,
+        },
+        Stream {
+            #[pin]
+            st: St,
+        },
+    }
+
+    impl<Fut, St> fmt::Debug for FlattenStream<Fut, St> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                FlattenStream::Future { .. } => "FlattenStream::Future",
+                FlattenStream::Stream { .. } => "FlattenStream::Stream",
+            })
+        }
+    }
+
+    impl<Fut, St> Stream for FlattenStream<Fut, St>
+    where
+        Fut: Future<Output = St>,
+        St: Stream,
+    {
+        type Item = St::Item;
+
+        fn poll_next(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            loop {
+                match self.as_mut().project() {
+                    FlattenStreamProj::Future { fut } => {
+                        let Poll::Ready(st) = fut.poll(cx) else {
+                            return Poll::Pending;
+                        };
+                        self.set(FlattenStream::Stream { st });
+                    }
+                    FlattenStreamProj::Stream { st } => return st.poll_next(cx),
+                }
+            }
+        }
+    }
+}
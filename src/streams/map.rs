@@ -0,0 +1,63 @@
+//! Transform each item of a stream with a closure.
+//!
+//! Unlike [`two_ways::map`](crate::two_ways::map), where the closure runs once
+//! and is consumed, here it runs once per item. So it is an `FnMut` held by
+//! value for as long as the stream lives, rather than an `FnOnce` in an
+//! `Option` waiting to be taken.
+//!
+//! There is no `auto` version, for want of `async` generators.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::streams::{collect, count::manual::count, map::manual};
+//!
+//! let squares = manual::map(count(5), |n| n * n);
+//! assert_eq!(block_on(collect(squares)), [0, 1, 4, 9, 16]);
+//! ```
+
+pub mod manual {
+    use core::{
+        fmt,
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use crate::streams::Stream;
+
+    pub fn map<St, F, T>(stream: St, f: F) -> Map<St, F>
+    where
+        St: Stream,
+        F: FnMut(St::Item) -> T,
+    {
+        Map { stream, f }
+    }
+
+    #[pin_project]
+    pub struct Map<St, F> {
+        #[pin]
+        stream: St,
+        f: F,
+    }
+
+    impl<St, F> fmt::Debug for Map<St, F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Map").finish_non_exhaustive()
+        }
+    }
+
+    impl<St, F, T> Stream for Map<St, F>
+    where
+        St: Stream,
+        F: FnMut(St::Item) -> T,
+    {
+        type Item = T;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.project();
+            let item = ready!(this.stream.poll_next(cx));
+            Poll::Ready(item.map(this.f))
+        }
+    }
+}
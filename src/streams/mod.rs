@@ -0,0 +1,115 @@
+//! A [`Stream`] is to an iterator what a [`Future`] is to a plain value: each
+//! item may not be ready yet, so asking for the next one can return
+//! [`Poll::Pending`] and arrange a wakeup, just like polling a future.
+//!
+//! The modules here follow the same shape as [`two_ways`](crate::two_ways):
+//!
+//! ```
+//! pub mod operation {
+//!     //! Where both exist, the streams in the auto and manual modules yield
+//!     //! the same items.
+//!
+//!     pub mod auto {
+//!         // the stream built from async-await syntax and existing pieces.
+//!     }
+//!
+//!     pub mod manual {
+//!         // a struct which implements [`Stream`] by hand.
+//!     }
+//! }
+//! ```
+//!
+//! Stable Rust has no `async` generators, so a stream can't be written the
+//! way an `async fn` writes a future. Where there's nothing to build the
+//! stream out of, there is only a `manual` version.
+//!
+//! [`Future`]: core::future::Future
+
+pub mod count;
+pub mod cycle;
+pub mod flatten;
+pub mod map;
+pub mod take;
+
+use alloc::vec::Vec;
+use core::{
+    fmt,
+    future::{poll_fn, Future},
+    pin::{pin, Pin},
+    task::{ready, Context, Poll},
+};
+
+/// The same trait as `futures_core::Stream`, defined here so nothing about
+/// it is hidden behind a dependency.
+pub trait Stream {
+    type Item;
+
+    /// `Ready(Some(item))` yields the next item, and `Ready(None)` means the
+    /// stream has ended. `Pending` means the same as it does for a future:
+    /// there's no item yet, and the task will be woken once there might be.
+    fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>>;
+}
+
+/// Convenience methods for every [`Stream`], the way `Iterator` has them
+/// built in.
+pub trait StreamExt: Stream {
+    /// A future resolving to the next item, or `None` once the stream has
+    /// ended.
+    ///
+    /// ```
+    /// # use futures_lite::future::block_on;
+    /// use async_intuition::streams::{count::manual::count, StreamExt};
+    ///
+    /// let mut counter = count(5);
+    /// let sum = block_on(async {
+    ///     let mut sum = 0;
+    ///     while let Some(n) = counter.next().await {
+    ///         sum += n;
+    ///     }
+    ///     sum
+    /// });
+    /// assert_eq!(sum, 10);
+    /// ```
+    fn next(&mut self) -> Next<'_, Self>
+    where
+        Self: Unpin,
+    {
+        Next { stream: self }
+    }
+}
+
+impl<St: Stream + ?Sized> StreamExt for St {}
+
+// only borrows the stream, so moving the `Next` never moves the stream. That
+// is also why the stream has to be `Unpin`: `Next` can't promise it stays put
+// between one `next` call and the following one.
+pub struct Next<'a, St: ?Sized> {
+    stream: &'a mut St,
+}
+
+impl<St: ?Sized> fmt::Debug for Next<'_, St> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Next").finish_non_exhaustive()
+    }
+}
+
+impl<St: Stream + Unpin + ?Sized> Future for Next<'_, St> {
+    type Output = Option<St::Item>;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        Pin::new(&mut *self.stream).poll_next(cx)
+    }
+}
+
+/// Drive `stream` to the end, gathering every item it yields.
+pub async fn collect<St: Stream>(stream: St) -> Vec<St::Item> {
+    let mut stream = pin!(stream);
+    let mut items = Vec::new();
+    poll_fn(|cx| loop {
+        match ready!(stream.as_mut().poll_next(cx)) {
+            Some(item) => items.push(item),
+            None => return Poll::Ready(core::mem::take(&mut items)),
+        }
+    })
+    .await
+}
@@ -0,0 +1,87 @@
+//! Yield at most the first `n` items of a stream, then end.
+//!
+//! Once the limit is reached, the inner stream is never polled again. Polling
+//! it one more time just to find out there's no need would be wasted work at
+//! best, and at worst would start something (a request, a timer) which then
+//! has to be thrown away.
+//!
+//! ```
+//! # use std::{pin::Pin, task::{Context, Poll}};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::streams::{collect, count::manual::count, take::manual, Stream, StreamExt};
+//!
+//! let endless = count(usize::MAX);
+//! assert_eq!(block_on(collect(manual::take(endless, 3))), [0, 1, 2]);
+//!
+//! // and once ended, it stays ended.
+//! let mut one = manual::take(count(usize::MAX), 1);
+//! assert_eq!(block_on(one.next()), Some(0));
+//! assert_eq!(block_on(one.next()), None);
+//! assert_eq!(block_on(one.next()), None);
+//!
+//! struct Untouchable;
+//!
+//! impl Stream for Untouchable {
+//!     type Item = ();
+//!
+//!     fn poll_next(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<Option<()>> {
+//!         panic!("polled the inner stream");
+//!     }
+//! }
+//!
+//! assert_eq!(block_on(collect(manual::take(Untouchable, 0))), []);
+//! ```
+
+pub mod manual {
+    use core::{
+        fmt,
+        pin::Pin,
+        task::{ready, Context, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use crate::streams::Stream;
+
+    pub fn take<St: Stream>(stream: St, n: usize) -> Take<St> {
+        Take {
+            stream,
+            remaining: n,
+        }
+    }
+
+    #[pin_project]
+    pub struct Take<St> {
+        #[pin]
+        stream: St,
+        remaining: usize,
+    }
+
+    impl<St> fmt::Debug for Take<St> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Take")
+                .field("remaining", &self.remaining)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<St: Stream> Stream for Take<St> {
+        type Item = St::Item;
+
+        fn poll_next(self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Option<Self::Item>> {
+            let this = self.project();
+            if *this.remaining == 0 {
+                return Poll::Ready(None);
+            }
+
+            let item = ready!(this.stream.poll_next(cx));
+            // an inner stream which ends early ends the `Take` too, and
+            // zeroing the count means it won't be polled again afterwards.
+            *this.remaining = match item {
+                Some(_) => *this.remaining - 1,
+                None => 0,
+            };
+            Poll::Ready(item)
+        }
+    }
+}
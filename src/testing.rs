@@ -0,0 +1,282 @@
+//! Tools for polling futures by hand, one step at a time.
+//!
+//! `block_on` only reports the final output, which hides exactly the things a
+//! hand-written future can get wrong: returning `Pending` without arranging a
+//! wakeup, or waking more often than it needs to. Polling through a
+//! [`RecordingWaker`] makes both visible.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::{Context, Poll}};
+//! use async_intuition::{basics::yield_now, testing::RecordingWaker};
+//!
+//! let recorder = RecordingWaker::new();
+//! let waker = recorder.waker();
+//! let mut cx = Context::from_waker(&waker);
+//! let mut fut = pin!(yield_now());
+//!
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(recorder.wakes_by_ref(), 1);
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(()));
+//! // completing doesn't need another wakeup.
+//! assert_eq!(recorder.wakes_by_ref(), 1);
+//! assert_eq!(recorder.wakes(), 0);
+//! assert_eq!(recorder.clones(), 0);
+//! ```
+//!
+//! When the wakeups don't matter, [`poll_once`] polls with a waker which
+//! ignores them:
+//!
+//! ```
+//! # use std::{pin::pin, task::Poll};
+//! use async_intuition::{basics::yield_now, testing::poll_once};
+//!
+//! let mut fut = pin!(yield_now());
+//! assert_eq!(poll_once(fut.as_mut()), Poll::Pending);
+//! assert_eq!(poll_once(fut.as_mut()), Poll::Ready(()));
+//! ```
+//!
+//! [`PollCounter`] stands in for the inner futures of a combinator: it takes
+//! exactly as many polls as it's told to, without depending on a clock.
+//!
+//! ```
+//! # use std::{pin::pin, task::Poll};
+//! use async_intuition::testing::{poll_counter, poll_once};
+//!
+//! let mut fut = pin!(poll_counter(2, 'x'));
+//! assert_eq!(poll_once(fut.as_mut()), Poll::Pending);
+//! assert_eq!(poll_once(fut.as_mut()), Poll::Pending);
+//! assert_eq!(poll_once(fut.as_mut()), Poll::Ready('x'));
+//! assert_eq!(fut.polls(), 3);
+//! ```
+//!
+//! Timers needn't wait on the wall clock either. A [`MockClock`] only moves
+//! when [`advanced`](MockClock::advance), firing whichever of its sleeps are
+//! due as it goes:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use std::{pin::pin, task::Poll, time::Duration};
+//! use async_intuition::testing::{poll_once, MockClock};
+//!
+//! let clock = MockClock::new();
+//! let start = clock.now();
+//! let mut sleep = pin!(clock.sleep(Duration::from_secs(1_000)));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Pending);
+//!
+//! clock.advance(Duration::from_secs(999));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Pending);
+//! clock.advance(Duration::from_secs(1));
+//! assert_eq!(poll_once(sleep.as_mut()), Poll::Ready(()));
+//! assert_eq!(clock.now() - start, Duration::from_secs(1_000));
+//! # }
+//! ```
+
+use alloc::sync::Arc;
+use core::{
+    fmt,
+    future::Future,
+    mem::ManuallyDrop,
+    pin::Pin,
+    sync::atomic::{AtomicUsize, Ordering},
+    task::{Context, Poll, RawWaker, RawWakerVTable, Waker},
+};
+#[cfg(feature = "std")]
+use std::{
+    sync::Mutex,
+    time::{Duration, Instant},
+};
+
+#[cfg(feature = "std")]
+use crate::basics::{
+    reactor::{Reactor, TimerId, VirtualReactor},
+    Sleep,
+};
+
+/// Counts how many times its wakers are woken, by value and by reference,
+/// and how many times they are cloned.
+#[derive(Debug, Default)]
+pub struct RecordingWaker {
+    wakes: AtomicUsize,
+    wakes_by_ref: AtomicUsize,
+    clones: AtomicUsize,
+}
+
+impl RecordingWaker {
+    pub fn new() -> Arc<Self> {
+        Arc::default()
+    }
+
+    /// A waker which records into `self`. Clones of it record here too.
+    pub fn waker(self: &Arc<Self>) -> Waker {
+        // `Waker::from` would do, if not for counting clones, which takes a
+        // vtable of our own (see `basics::waker` for how these work).
+        let data = Arc::into_raw(Arc::clone(self)).cast::<()>();
+        // SAFETY: `data` came from `Arc::into_raw`, as `VTABLE` expects.
+        unsafe { Waker::from_raw(RawWaker::new(data, &VTABLE)) }
+    }
+
+    /// Number of calls to [`Waker::wake`].
+    pub fn wakes(&self) -> usize {
+        self.wakes.load(Ordering::SeqCst)
+    }
+
+    /// Number of calls to [`Waker::wake_by_ref`].
+    pub fn wakes_by_ref(&self) -> usize {
+        self.wakes_by_ref.load(Ordering::SeqCst)
+    }
+
+    /// Number of times a waker was cloned.
+    pub fn clones(&self) -> usize {
+        self.clones.load(Ordering::SeqCst)
+    }
+}
+
+static VTABLE: RawWakerVTable = RawWakerVTable::new(clone, wake, wake_by_ref, drop);
+
+// as in `basics::waker`, every `data` pointer came from
+// `Arc::<RecordingWaker>::into_raw` and owns one strong count.
+
+unsafe fn clone(data: *const ()) -> RawWaker {
+    let recorder = ManuallyDrop::new(unsafe { Arc::from_raw(data.cast::<RecordingWaker>()) });
+    recorder.clones.fetch_add(1, Ordering::SeqCst);
+    RawWaker::new(Arc::into_raw(Arc::clone(&recorder)).cast(), &VTABLE)
+}
+
+unsafe fn wake(data: *const ()) {
+    let recorder = unsafe { Arc::from_raw(data.cast::<RecordingWaker>()) };
+    recorder.wakes.fetch_add(1, Ordering::SeqCst);
+}
+
+unsafe fn wake_by_ref(data: *const ()) {
+    let recorder = unsafe { &*data.cast::<RecordingWaker>() };
+    recorder.wakes_by_ref.fetch_add(1, Ordering::SeqCst);
+}
+
+unsafe fn drop(data: *const ()) {
+    unsafe { Arc::decrement_strong_count(data.cast::<RecordingWaker>()) };
+}
+
+/// Returns `Pending` on the first `n` polls, waking itself each time so that
+/// an executor comes straight back, then `Ready(value)`.
+pub fn poll_counter<T>(n: usize, value: T) -> PollCounter<T> {
+    PollCounter {
+        pending_left: n,
+        value: Some(value),
+        polls: 0,
+    }
+}
+
+pub struct PollCounter<T> {
+    pending_left: usize,
+    value: Option<T>,
+    polls: usize,
+}
+
+impl<T> fmt::Debug for PollCounter<T> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("PollCounter")
+            .field("pending_left", &self.pending_left)
+            .field("polls", &self.polls)
+            .finish_non_exhaustive()
+    }
+}
+
+// like `Ready`, the value is only ever moved out, never pinned.
+impl<T> Unpin for PollCounter<T> {}
+
+impl<T> PollCounter<T> {
+    /// How many times the future has been polled so far.
+    pub fn polls(&self) -> usize {
+        self.polls
+    }
+}
+
+impl<T> Future for PollCounter<T> {
+    type Output = T;
+
+    fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+        self.polls += 1;
+        if self.pending_left > 0 {
+            self.pending_left -= 1;
+            cx.waker().wake_by_ref();
+            return Poll::Pending;
+        }
+        let value = self
+            .value
+            .take()
+            .expect("`PollCounter` polled after completion");
+        Poll::Ready(value)
+    }
+}
+
+/// A context whose waker does nothing when woken.
+pub fn noop_context() -> Context<'static> {
+    Context::from_waker(Waker::noop())
+}
+
+/// Poll `fut` a single time with [`noop_context`].
+pub fn poll_once<F: Future>(fut: Pin<&mut F>) -> Poll<F::Output> {
+    fut.poll(&mut noop_context())
+}
+
+/// A clock which stands still until [`advance`](MockClock::advance)d, and a
+/// [`Reactor`] whose timers go by it rather than by real time.
+#[cfg(feature = "std")]
+pub struct MockClock {
+    now: Mutex<Instant>,
+    timers: VirtualReactor,
+}
+
+#[cfg(feature = "std")]
+impl Default for MockClock {
+    fn default() -> Self {
+        Self::new()
+    }
+}
+
+#[cfg(feature = "std")]
+impl MockClock {
+    /// A clock starting from the real current time, which it then leaves
+    /// behind.
+    pub fn new() -> Self {
+        MockClock {
+            now: Mutex::new(Instant::now()),
+            timers: VirtualReactor::new(),
+        }
+    }
+
+    pub fn now(&self) -> Instant {
+        *self.now.lock().unwrap()
+    }
+
+    /// Move the clock forward by `by`, firing every timer which is due by the
+    /// new time before returning.
+    pub fn advance(&self, by: Duration) {
+        let now = {
+            let mut now = self.now.lock().unwrap();
+            *now += by;
+            *now
+        };
+        self.timers.fire_until(now);
+    }
+
+    /// A [`Sleep`] lasting `duration` by this clock.
+    pub fn sleep(&self, duration: Duration) -> Sleep<&Self> {
+        Sleep::with_reactor(self.now() + duration, self)
+    }
+}
+
+#[cfg(feature = "std")]
+impl Reactor for MockClock {
+    fn register(&self, deadline: Instant, waker: Waker) -> TimerId {
+        self.timers.register(deadline, waker)
+    }
+
+    fn deregister(&self, id: TimerId) {
+        self.timers.deregister(id)
+    }
+
+    fn now(&self) -> Instant {
+        MockClock::now(self)
+    }
+}
@@ -1,3 +1,72 @@
+//! Run one future to completion, then another.
+//!
+//! With inner futures which take a known number of polls, the number of polls
+//! the combined future takes is known too: one poll per `Pending` of either
+//! inner future, plus the final one. Both versions take the same number, as
+//! when `a` finishes they move straight on to polling `b` within that poll.
+//! Returning `Pending` there instead would cost an extra poll at best, and at
+//! worst hang, since nothing would have arranged for the task to be woken.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin};
+//! use async_intuition::{
+//!     testing::{poll_counter, poll_once},
+//!     two_ways::a_then_b::{auto, manual},
+//! };
+//!
+//! fn polls_to_finish(fut: impl Future<Output = ()>) -> usize {
+//!     let mut fut = pin!(fut);
+//!     let mut polls = 1;
+//!     while poll_once(fut.as_mut()).is_pending() {
+//!         polls += 1;
+//!     }
+//!     polls
+//! }
+//!
+//! assert_eq!(polls_to_finish(auto::a_then_b(poll_counter(2, ()), poll_counter(3, ()))), 6);
+//! assert_eq!(polls_to_finish(manual::a_then_b(poll_counter(2, ()), poll_counter(3, ()))), 6);
+//! ```
+//!
+//! The `Debug` output of [`manual::DoAThenB`] shows which future it is on,
+//! without needing either of them to be `Debug`:
+//!
+//! ```
+//! # use std::pin::pin;
+//! use async_intuition::{
+//!     basics::ready,
+//!     testing::{poll_counter, poll_once},
+//!     two_ways::a_then_b::manual::DoAThenB,
+//! };
+//!
+//! let fresh = DoAThenB::new(ready(()), ready(()));
+//! assert_eq!(format!("{fresh:?}"), "DoAThenB { state: DoingA, .. }");
+//!
+//! let mut fut = pin!(DoAThenB::new(ready(()), poll_counter(1, ())));
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert_eq!(format!("{fut:?}"), "DoAThenB { state: DoingB, .. }");
+//! ```
+//!
+//! Once `b` has finished too, it is in a state of its own, and as a
+//! [`FusedFuture`] it says so:
+//!
+//! ```
+//! # use std::pin::pin;
+//! use async_intuition::{
+//!     basics::ready,
+//!     testing::{poll_counter, poll_once},
+//!     two_ways::{a_then_b::manual::DoAThenB, fuse::FusedFuture},
+//! };
+//!
+//! let mut fut = pin!(DoAThenB::new(poll_counter(1, ()), ready(())));
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert!(!fut.is_terminated());
+//! assert!(poll_once(fut.as_mut()).is_ready());
+//! assert!(fut.is_terminated());
+//! assert_eq!(format!("{fut:?}"), "DoAThenB { state: Done, .. }");
+//! ```
+//!
+//! [`FusedFuture`]: crate::two_ways::fuse::FusedFuture
+
 pub mod auto {
     use core::future::Future;
 
@@ -9,27 +78,30 @@ pub mod auto {
 
 pub mod manual {
     use core::{
+        fmt,
         future::Future,
-        pin::{pin, Pin},
-        task::Poll,
+        pin::Pin,
+        task::{ready, Poll},
     };
 
     use pin_project::pin_project;
 
+    use crate::two_ways::fuse::FusedFuture;
+
     pub async fn a_then_b<A: Future<Output = ()>, B: Future<Output = ()>>(a: A, b: B) {
-        let state = State::default();
-        DoAThenB { state, a, b }.await
+        DoAThenB::new(a, b).await
     }
 
-    #[derive(Clone, Copy, Default)]
+    #[derive(Clone, Copy, Debug, Default)]
     enum State {
         #[default]
         DoingA,
         DoingB,
+        Done,
     }
 
     #[pin_project]
-    struct DoAThenB<A, B> {
+    pub struct DoAThenB<A, B> {
         state: State,
         #[pin]
         a: A,
@@ -37,21 +109,52 @@ pub mod manual {
         b: B,
     }
 
+    impl<A, B> DoAThenB<A, B> {
+        /// The future behind [`a_then_b`], for when its state needs to be
+        /// looked at, or it needs to be asked whether it has finished.
+        pub fn new(a: A, b: B) -> Self {
+            let state = State::default();
+            DoAThenB { state, a, b }
+        }
+    }
+
+    // prints the state only, so neither future has to be `Debug`.
+    impl<A, B> fmt::Debug for DoAThenB<A, B> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("DoAThenB")
+                .field("state", &self.state)
+                .finish_non_exhaustive()
+        }
+    }
+
     impl<A: Future<Output = ()>, B: Future<Output = ()>> Future for DoAThenB<A, B> {
         type Output = ();
 
-        fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-            let this = self.project();
-            match this.state {
-                State::DoingA => {
-                    if this.a.poll(cx).is_ready() {
-                        // TODO: drop a?
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            loop {
+                match this.state {
+                    State::DoingA => {
+                        ready!(this.a.as_mut().poll(cx));
+                        // `a` stays put until the whole `DoAThenB` is dropped.
+                        // It is pinned in place, so ending it early takes
+                        // `ManuallyDrop` and `unsafe`, as in `chain3`.
                         *this.state = State::DoingB;
                     }
-                    Poll::Pending
+                    State::DoingB => {
+                        ready!(this.b.as_mut().poll(cx));
+                        *this.state = State::Done;
+                        return Poll::Ready(());
+                    }
+                    State::Done => panic!("`async fn` resumed after completion"),
                 }
-                State::DoingB => this.b.poll(cx),
             }
         }
     }
+
+    impl<A: Future<Output = ()>, B: Future<Output = ()>> FusedFuture for DoAThenB<A, B> {
+        fn is_terminated(&self) -> bool {
+            matches!(self.state, State::Done)
+        }
+    }
 }
@@ -0,0 +1,130 @@
+//! Wrap a future so it can be cancelled from the outside, through a handle.
+//!
+//! Dropping a future already cancels it, but only whoever owns the future can
+//! do that. An [`AbortHandle`](manual::AbortHandle) can be sent anywhere. When
+//! aborted, it flags the shared state and wakes the task, whose next poll of
+//! the [`Abortable`](manual::Abortable) resolves to `Err(Aborted)` without
+//! polling the inner future again.
+//!
+//! There is no `auto` version, since the abort has to be noticed between
+//! polls of the inner future.
+//!
+//! ```
+//! # use std::{thread, time::{Duration, Instant}};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{basics::sleep, two_ways::abortable::{manual, Aborted}};
+//!
+//! let (fut, handle) = manual::abortable(sleep(Duration::from_secs(60)));
+//! thread::spawn(move || {
+//!     thread::sleep(Duration::from_millis(10));
+//!     handle.abort();
+//! });
+//!
+//! // done as soon as the abort lands, a long way short of the sleep.
+//! let start = Instant::now();
+//! assert_eq!(block_on(fut), Err(Aborted));
+//! assert!(start.elapsed() < Duration::from_secs(1));
+//! ```
+
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Aborted;
+
+impl fmt::Display for Aborted {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("future was aborted")
+    }
+}
+
+impl core::error::Error for Aborted {}
+
+pub mod manual {
+    use core::{fmt, future::Future, pin::Pin, task::Poll};
+    use std::{
+        sync::{
+            atomic::{AtomicBool, Ordering},
+            Arc, Mutex,
+        },
+        task::Waker,
+    };
+
+    use pin_project::pin_project;
+
+    use super::Aborted;
+
+    pub fn abortable<Fut: Future>(fut: Fut) -> (Abortable<Fut>, AbortHandle) {
+        let inner = Arc::new(AbortInner {
+            aborted: AtomicBool::new(false),
+            waker: Mutex::new(None),
+        });
+        let handle = AbortHandle {
+            inner: Arc::clone(&inner),
+        };
+        (Abortable { fut, inner }, handle)
+    }
+
+    #[pin_project]
+    pub struct Abortable<Fut> {
+        #[pin]
+        fut: Fut,
+        inner: Arc<AbortInner>,
+    }
+
+    impl<Fut> fmt::Debug for Abortable<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Abortable")
+                .field("aborted", &self.inner.aborted.load(Ordering::SeqCst))
+                .finish_non_exhaustive()
+        }
+    }
+
+    #[derive(Clone)]
+    pub struct AbortHandle {
+        inner: Arc<AbortInner>,
+    }
+
+    struct AbortInner {
+        aborted: AtomicBool,
+        // the waker from the `Abortable`'s latest poll.
+        waker: Mutex<Option<Waker>>,
+    }
+
+    impl AbortHandle {
+        pub fn abort(&self) {
+            self.inner.aborted.store(true, Ordering::Release);
+            // taken in a statement of its own, so the lock is released
+            // before waking: a waker which polls the task right away would
+            // otherwise deadlock in `Abortable::poll`.
+            let waker = self.inner.waker.lock().unwrap().take();
+            if let Some(waker) = waker {
+                waker.wake();
+            }
+        }
+    }
+
+    impl<Fut: Future> Future for Abortable<Fut> {
+        type Output = Result<Fut::Output, Aborted>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if this.inner.aborted.load(Ordering::Acquire) {
+                return Poll::Ready(Err(Aborted));
+            }
+
+            let mut waker = this.inner.waker.lock().unwrap();
+            match &mut *waker {
+                Some(waker) => waker.clone_from(cx.waker()),
+                None => *waker = Some(cx.waker().clone()),
+            }
+            drop(waker);
+            // an abort between the check above and storing the waker would
+            // have found no waker to wake, so check once more.
+            if this.inner.aborted.load(Ordering::Acquire) {
+                return Poll::Ready(Err(Aborted));
+            }
+
+            this.fut.poll(cx).map(Ok)
+        }
+    }
+}
@@ -0,0 +1,99 @@
+//! Run a fallible future and, only if it succeeds, feed its value to a closure
+//! producing a second fallible future. An `Err` from the first future is
+//! returned as is, and the closure is never called.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::and_then::{auto, manual};
+//!
+//! let parse = |s: &'static str| async move { s.parse::<u32>().map_err(|_| "not a number") };
+//! let double = |n: u32| async move { n.checked_mul(2).ok_or("overflow") };
+//! assert_eq!(block_on(auto::and_then(parse("21"), double)), Ok(42));
+//! assert_eq!(block_on(manual::and_then(parse("21"), double)), Ok(42));
+//!
+//! let unreachable = |_: u32| async { panic!("second stage ran after an `Err`") };
+//! assert_eq!(block_on(auto::and_then(parse("x"), unreachable)), Err::<(), _>("not a number"));
+//! assert_eq!(block_on(manual::and_then(parse("x"), unreachable)), Err::<(), _>("not a number"));
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn and_then<A, F, B, T, U, E>(a: A, f: F) -> Result<U, E>
+    where
+        A: Future<Output = Result<T, E>>,
+        F: FnOnce(T) -> B,
+        B: Future<Output = Result<U, E>>,
+    {
+        match a.await {
+            Ok(t) => f(t).await,
+            Err(e) => Err(e),
+        }
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn and_then<A, F, B, T, U, E>(a: A, f: F) -> Result<U, E>
+    where
+        A: Future<Output = Result<T, E>>,
+        F: FnOnce(T) -> B,
+        B: Future<Output = Result<U, E>>,
+    {
+        AndThen::First { a, f: Some(f) }.await
+    }
+
+    #[pin_project(project = AndThenProj)]
+    enum AndThen<A, F, B> {
+        First {
+            #[pin]
+            a: A,
+            f: Option<F>,
+        },
+        Second {
+            #[pin]
+            b: B,
+        },
+        Done,
+    }
+
+    impl<A, F, B, T, U, E> Future for AndThen<A, F, B>
+    where
+        A: Future<Output = Result<T, E>>,
+        F: FnOnce(T) -> B,
+        B: Future<Output = Result<U, E>>,
+    {
+        type Output = Result<U, E>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            loop {
+                match self.as_mut().project() {
+                    AndThenProj::First { a, f } => match a.poll(cx) {
+                        Poll::Ready(Ok(t)) => {
+                            let f = f.take().expect("`f` is only taken when leaving `First`");
+                            self.set(AndThen::Second { b: f(t) });
+                        }
+                        Poll::Ready(Err(e)) => {
+                            // skip straight past `Second`: `f` is dropped
+                            // without ever being called.
+                            self.set(AndThen::Done);
+                            return Poll::Ready(Err(e));
+                        }
+                        Poll::Pending => return Poll::Pending,
+                    },
+                    AndThenProj::Second { b } => {
+                        let Poll::Ready(out) = b.poll(cx) else {
+                            return Poll::Pending;
+                        };
+                        self.set(AndThen::Done);
+                        return Poll::Ready(out);
+                    }
+                    AndThenProj::Done => panic!("`async fn` resumed after completion"),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,109 @@
+//! Await futures produced by a closure one after another, gathering their
+//! outputs until there are `n` of them.
+//!
+//! The same lazily recreated future as [`until_equals`](super::until_equals),
+//! but each output is kept rather than compared. A batch of zero never calls
+//! the closure at all.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::batch::{auto, manual};
+//!
+//! let counter = Cell::new(0);
+//! let next = || async {
+//!     yield_now().await;
+//!     counter.set(counter.get() + 1);
+//!     counter.get()
+//! };
+//!
+//! assert_eq!(block_on(auto::batch(4, next)), [1, 2, 3, 4]);
+//! assert_eq!(block_on(manual::batch(4, next)), [5, 6, 7, 8]);
+//! assert!(block_on(manual::batch(0, next)).is_empty());
+//! assert_eq!(counter.get(), 8);
+//! ```
+
+pub mod auto {
+    use alloc::vec::Vec;
+    use core::future::Future;
+
+    pub async fn batch<F, Fut>(n: usize, mut f: F) -> Vec<Fut::Output>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future,
+    {
+        let mut items = Vec::with_capacity(n);
+        while items.len() < n {
+            items.push(f().await);
+        }
+        items
+    }
+}
+
+pub mod manual {
+    use alloc::vec::Vec;
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn batch<F, Fut>(n: usize, f: F) -> Vec<Fut::Output>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future,
+    {
+        Batch {
+            n,
+            f,
+            items: Some(Vec::with_capacity(n)),
+            fut: None,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Batch<F, Fut: Future> {
+        n: usize,
+        f: F,
+        // `None` once handed back as the output.
+        items: Option<Vec<Fut::Output>>,
+        // the future producing the next item, if one is in flight.
+        #[pin]
+        fut: Option<Fut>,
+    }
+
+    impl<F, Fut> Future for Batch<F, Fut>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future,
+    {
+        type Output = Vec<Fut::Output>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            loop {
+                let items = this
+                    .items
+                    .as_mut()
+                    .expect("`async fn` resumed after completion");
+                if items.len() == *this.n {
+                    return Poll::Ready(this.items.take().expect("checked above"));
+                }
+
+                let fut = match this.fut.as_mut().as_pin_mut() {
+                    Some(fut) => fut,
+                    None => {
+                        this.fut.set(Some((this.f)()));
+                        this.fut.as_mut().as_pin_mut().expect("just set")
+                    }
+                };
+                let item = ready!(fut.poll(cx));
+                this.fut.set(None);
+                this.items.as_mut().expect("checked above").push(item);
+            }
+        }
+    }
+}
@@ -0,0 +1,91 @@
+//! Erase a future's type by moving it to the heap behind a `dyn Future`.
+//!
+//! Every `async` block has its own anonymous type, so two different futures
+//! can't share a `Vec` or be returned from the two arms of an `if` without
+//! first being erased to a common type:
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use std::{future::Future, pin::Pin, time::Duration};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{
+//!     basics::{ready, sleep},
+//!     two_ways::{boxed::auto, join_all},
+//! };
+//!
+//! let futs: Vec<Pin<Box<dyn Future<Output = ()>>>> = vec![
+//!     auto::boxed(sleep(Duration::from_millis(10))),
+//!     auto::boxed(ready(())),
+//! ];
+//! assert_eq!(block_on(join_all::manual::join_all(futs)), [(), ()]);
+//! # }
+//! ```
+//!
+//! Polling goes through [`Future::poll`], which takes `Pin<&mut Self>`. For a
+//! `Box<dyn Future>`, `Self` is the `dyn Future`, and since the compiler can't
+//! know whether whatever is behind it is [`Unpin`], it won't pin it for us:
+//!
+//! ```compile_fail
+//! # use std::{future::Future, pin::Pin, task::{Context, Waker}};
+//! let mut fut: Box<dyn Future<Output = ()>> = Box::new(async {});
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let _ = Pin::new(&mut *fut).poll(&mut cx);
+//! ```
+//!
+//! Hence `Box::pin` rather than `Box::new`: the future is pinned the moment it
+//! lands on the heap, and moving the `Pin<Box<_>>` around only moves the
+//! pointer, never the future. Both versions resolve to the same output:
+//!
+//! ```
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::boxed::{auto, manual};
+//!
+//! let fut = || async {
+//!     yield_now().await;
+//!     5
+//! };
+//! assert_eq!(block_on(auto::boxed(fut())), 5);
+//! assert_eq!(block_on(manual::boxed(fut())), 5);
+//! ```
+//!
+//! [`Future::poll`]: core::future::Future::poll
+
+pub mod auto {
+    use alloc::boxed::Box;
+    use core::{future::Future, pin::Pin};
+
+    pub fn boxed<Fut: Future + 'static>(fut: Fut) -> Pin<Box<dyn Future<Output = Fut::Output>>> {
+        Box::pin(fut)
+    }
+}
+
+pub mod manual {
+    use alloc::boxed::Box;
+    use core::{fmt, future::Future, pin::Pin, task::Poll};
+
+    pub fn boxed<Fut: Future + 'static>(fut: Fut) -> Boxed<Fut::Output> {
+        let erased: Box<dyn Future<Output = Fut::Output>> = Box::new(fut);
+        // nothing but the box can reach the future, and the box never moves
+        // what it points to, so it's fine to declare it pinned from here on.
+        Boxed(Box::into_pin(erased))
+    }
+
+    pub struct Boxed<T>(Pin<Box<dyn Future<Output = T>>>);
+
+    impl<T> fmt::Debug for Boxed<T> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Boxed").finish_non_exhaustive()
+        }
+    }
+
+    impl<T> Future for Boxed<T> {
+        type Output = T;
+
+        // `Boxed` is `Unpin` (it's only a pointer), so `self` can be used
+        // mutably without any projection. The vtable call then receives the
+        // `Pin<&mut dyn Future>` it needs straight from the pinned box.
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            self.0.as_mut().poll(cx)
+        }
+    }
+}
@@ -0,0 +1,79 @@
+//! Wrap a future so that polling it again after it has completed reports the
+//! mistake as an error instead of panicking.
+//!
+//! This is the third answer to "what happens after `Ready`?", alongside the
+//! panic from `async fn` and [`fuse`](super::fuse)'s pending-forever. Every
+//! poll resolves, so a caller which loops on a finished future finds out
+//! right away rather than crashing or hanging.
+//!
+//! There is no `auto` version, as wrapping this in an `async fn` would put
+//! the panic right back.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! use async_intuition::two_ways::catch_completion::{manual, Completed};
+//!
+//! let mut fut = pin!(manual::catch_completion(async { 5 }));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Ok(5)));
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(Err(Completed)));
+//! ```
+
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Completed;
+
+impl fmt::Display for Completed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("future polled after completion")
+    }
+}
+
+impl core::error::Error for Completed {}
+
+pub mod manual {
+    use core::{
+        fmt,
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use super::Completed;
+
+    pub fn catch_completion<Fut: Future>(fut: Fut) -> CatchCompletion<Fut> {
+        CatchCompletion { fut, done: false }
+    }
+
+    #[pin_project]
+    pub struct CatchCompletion<Fut> {
+        #[pin]
+        fut: Fut,
+        done: bool,
+    }
+
+    impl<Fut> fmt::Debug for CatchCompletion<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("CatchCompletion")
+                .field("done", &self.done)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<Fut: Future> Future for CatchCompletion<Fut> {
+        type Output = Result<Fut::Output, Completed>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if *this.done {
+                return Poll::Ready(Err(Completed));
+            }
+            let output = ready!(this.fut.poll(cx));
+            *this.done = true;
+            Poll::Ready(Ok(output))
+        }
+    }
+}
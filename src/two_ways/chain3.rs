@@ -0,0 +1,254 @@
+//! Run three futures one after another: [`a_then_b`](super::a_then_b) with
+//! one more link.
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::chain3::{auto, manual};
+//!
+//! let order = RefCell::new(Vec::new());
+//! let step = |name| {
+//!     let order = &order;
+//!     async move {
+//!         yield_now().await;
+//!         order.borrow_mut().push(name);
+//!     }
+//! };
+//!
+//! block_on(auto::chain3(step('a'), step('b'), step('c')));
+//! block_on(manual::chain3(step('a'), step('b'), step('c')));
+//! assert_eq!(*order.borrow(), ['a', 'b', 'c', 'a', 'b', 'c']);
+//! ```
+//!
+//! The manual version drops each future the moment it finishes, rather than
+//! leaving it around until the whole chain is dropped. That means which of
+//! the three are still alive depends on how far the chain got, and dropping
+//! it early has to drop exactly those. With two futures there are two cases
+//! to get right; with three there are already four. Dropped before `a` has
+//! finished, all three go, each exactly once:
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! # use futures_lite::future::pending;
+//! use async_intuition::{testing::poll_once, two_ways::chain3::manual};
+//!
+//! thread_local! {
+//!     static DROPPED: RefCell<Vec<&'static str>> = RefCell::default();
+//! }
+//!
+//! struct Payload(&'static str);
+//!
+//! impl Drop for Payload {
+//!     fn drop(&mut self) {
+//!         DROPPED.with_borrow_mut(|dropped| dropped.push(self.0));
+//!     }
+//! }
+//!
+//! let holding = |name| {
+//!     let payload = Payload(name);
+//!     async move {
+//!         let _payload = payload;
+//!         pending::<()>().await;
+//!     }
+//! };
+//!
+//! let mut chain = Box::pin(manual::chain3(holding("a"), holding("b"), holding("c")));
+//! assert!(poll_once(chain.as_mut()).is_pending());
+//! drop(chain);
+//! assert_eq!(DROPPED.take(), ["a", "b", "c"]);
+//! ```
+//!
+//! A destructor can panic, too. The chain has to have moved on from a future
+//! before dropping it, or dropping the chain during the unwind would drop
+//! that future a second time:
+//!
+//! ```
+//! # use std::{cell::RefCell, future::Future, panic::{self, AssertUnwindSafe}, pin::Pin};
+//! # use std::task::{Context, Poll};
+//! # use futures_lite::future::pending;
+//! use async_intuition::{testing::poll_once, two_ways::chain3::manual};
+//!
+//! thread_local! {
+//!     static DROPPED: RefCell<Vec<&'static str>> = RefCell::default();
+//! }
+//!
+//! struct Payload(&'static str);
+//!
+//! impl Drop for Payload {
+//!     fn drop(&mut self) {
+//!         DROPPED.with_borrow_mut(|dropped| dropped.push(self.0));
+//!     }
+//! }
+//!
+//! // ready straight away, then panics when dropped.
+//! struct PanicsOnDrop;
+//!
+//! impl Future for PanicsOnDrop {
+//!     type Output = ();
+//!
+//!     fn poll(self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<()> {
+//!         Poll::Ready(())
+//!     }
+//! }
+//!
+//! impl Drop for PanicsOnDrop {
+//!     fn drop(&mut self) {
+//!         DROPPED.with_borrow_mut(|dropped| dropped.push("a"));
+//!         panic!("dropping a");
+//!     }
+//! }
+//!
+//! let holding = |name| {
+//!     let payload = Payload(name);
+//!     async move {
+//!         let _payload = payload;
+//!         pending::<()>().await;
+//!     }
+//! };
+//!
+//! let mut chain = Box::pin(manual::chain3(PanicsOnDrop, holding("b"), holding("c")));
+//! let polled = panic::catch_unwind(AssertUnwindSafe(|| poll_once(chain.as_mut())));
+//! assert!(polled.is_err());
+//! drop(chain);
+//! assert_eq!(DROPPED.take(), ["a", "b", "c"]);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn chain3<A, B, C>(a: A, b: B, c: C)
+    where
+        A: Future<Output = ()>,
+        B: Future<Output = ()>,
+        C: Future<Output = ()>,
+    {
+        a.await;
+        b.await;
+        c.await
+    }
+}
+
+pub mod manual {
+    use core::{
+        future::Future,
+        mem::ManuallyDrop,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::{pin_project, pinned_drop};
+
+    pub async fn chain3<A, B, C>(a: A, b: B, c: C)
+    where
+        A: Future<Output = ()>,
+        B: Future<Output = ()>,
+        C: Future<Output = ()>,
+    {
+        Chain3 {
+            state: State::DoingA,
+            a: ManuallyDrop::new(a),
+            b: ManuallyDrop::new(b),
+            c: ManuallyDrop::new(c),
+        }
+        .await
+    }
+
+    // a future is alive exactly until the state moves past it.
+    #[derive(Clone, Copy)]
+    enum State {
+        DoingA,
+        DoingB,
+        DoingC,
+        Done,
+    }
+
+    #[pin_project(PinnedDrop)]
+    struct Chain3<A, B, C> {
+        state: State,
+        #[pin]
+        a: ManuallyDrop<A>,
+        #[pin]
+        b: ManuallyDrop<B>,
+        #[pin]
+        c: ManuallyDrop<C>,
+    }
+
+    /// # Safety
+    ///
+    /// The value in `slot` must not have been dropped.
+    unsafe fn pinned<T>(slot: Pin<&mut ManuallyDrop<T>>) -> Pin<&mut T> {
+        unsafe { slot.map_unchecked_mut(|slot| &mut **slot) }
+    }
+
+    /// # Safety
+    ///
+    /// The value in `slot` must not have been dropped, and must never be
+    /// accessed again afterwards.
+    unsafe fn drop_pinned<T>(slot: Pin<&mut ManuallyDrop<T>>) {
+        // dropping in place upholds the pinning guarantee: the value is
+        // never moved before its destructor runs.
+        unsafe { ManuallyDrop::drop(slot.get_unchecked_mut()) }
+    }
+
+    impl<A, B, C> Future for Chain3<A, B, C>
+    where
+        A: Future<Output = ()>,
+        B: Future<Output = ()>,
+        C: Future<Output = ()>,
+    {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            // SAFETY (for every block below): each future is polled only in
+            // its own state, and that state is left just before the future is
+            // dropped. Even if its destructor panics, nothing (`PinnedDrop`
+            // included) touches it again.
+            loop {
+                match *this.state {
+                    State::DoingA => {
+                        ready!(unsafe { pinned(this.a.as_mut()) }.poll(cx));
+                        *this.state = State::DoingB;
+                        unsafe { drop_pinned(this.a.as_mut()) };
+                    }
+                    State::DoingB => {
+                        ready!(unsafe { pinned(this.b.as_mut()) }.poll(cx));
+                        *this.state = State::DoingC;
+                        unsafe { drop_pinned(this.b.as_mut()) };
+                    }
+                    State::DoingC => {
+                        ready!(unsafe { pinned(this.c.as_mut()) }.poll(cx));
+                        *this.state = State::Done;
+                        unsafe { drop_pinned(this.c.as_mut()) };
+                        return Poll::Ready(());
+                    }
+                    State::Done => panic!("`async fn` resumed after completion"),
+                }
+            }
+        }
+    }
+
+    #[pinned_drop]
+    impl<A, B, C> PinnedDrop for Chain3<A, B, C> {
+        fn drop(self: Pin<&mut Self>) {
+            let this = self.project();
+            // SAFETY: the state says which futures haven't been dropped yet,
+            // and after this none of them will be touched again.
+            unsafe {
+                match *this.state {
+                    State::DoingA => {
+                        drop_pinned(this.a);
+                        drop_pinned(this.b);
+                        drop_pinned(this.c);
+                    }
+                    State::DoingB => {
+                        drop_pinned(this.b);
+                        drop_pinned(this.c);
+                    }
+                    State::DoingC => drop_pinned(this.c),
+                    State::Done => {}
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,51 @@
+//! Count how many times a future is polled before it completes.
+//!
+//! There is no `auto` version: an `async` block can only `.await` the inner
+//! future, and each `.await` may poll it any number of times without the
+//! block ever finding out. Observing individual polls takes a hand-written
+//! [`Future`](std::future::Future) impl sitting between the executor and the
+//! inner future.
+//!
+//! ```
+//! # use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::count_polls::manual;
+//!
+//! let polls = Arc::new(AtomicUsize::new(0));
+//! block_on(manual::count_polls(yield_now(), Arc::clone(&polls)));
+//! // one poll to yield and another to complete.
+//! assert_eq!(polls.load(Ordering::Relaxed), 2);
+//! ```
+
+pub mod manual {
+    use alloc::sync::Arc;
+    use core::{
+        future::Future,
+        pin::Pin,
+        sync::atomic::{AtomicUsize, Ordering},
+        task::Poll,
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn count_polls<Fut: Future>(fut: Fut, polls: Arc<AtomicUsize>) -> Fut::Output {
+        CountPolls { fut, polls }.await
+    }
+
+    #[pin_project]
+    struct CountPolls<Fut> {
+        #[pin]
+        fut: Fut,
+        polls: Arc<AtomicUsize>,
+    }
+
+    impl<Fut: Future> Future for CountPolls<Fut> {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            this.polls.fetch_add(1, Ordering::Relaxed);
+            this.fut.poll(cx)
+        }
+    }
+}
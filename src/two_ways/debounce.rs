@@ -0,0 +1,135 @@
+//! Keep awaiting events produced by `next`, resolving to the latest one once
+//! `quiet` has passed without another arriving. Each new event restarts the
+//! wait, so a burst of events resolves only after the burst is over, and to
+//! its final event.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use futures_lite::future::{block_on, pending};
+//! use async_intuition::{basics::sleep, two_ways::debounce::{auto, manual}};
+//!
+//! // event `i` arrives `gaps[i]` milliseconds after the one before it.
+//! let events = |gaps: &'static [u64]| {
+//!     let mut gaps = gaps.iter().enumerate();
+//!     move || {
+//!         let next = gaps.next();
+//!         async move {
+//!             let Some((i, &gap)) = next else {
+//!                 return pending().await;
+//!             };
+//!             sleep(Duration::from_millis(gap)).await;
+//!             i
+//!         }
+//!     }
+//! };
+//! let quiet = Duration::from_millis(200);
+//!
+//! // a burst: every event lands well within the quiet period of the last.
+//! let burst = &[0, 5, 5, 5, 5];
+//! assert_eq!(block_on(auto::debounce(events(burst), quiet)), 4);
+//! assert_eq!(block_on(manual::debounce(events(burst), quiet)), 4);
+//!
+//! // the third event comes too late to count.
+//! let late = &[0, 5, 500];
+//! assert_eq!(block_on(auto::debounce(events(late), quiet)), 1);
+//! assert_eq!(block_on(manual::debounce(events(late), quiet)), 1);
+//! ```
+
+pub mod auto {
+    use std::{future::Future, time::Duration};
+
+    use crate::basics::sleep;
+
+    pub async fn debounce<EventFut, GetNextFut>(
+        mut next: GetNextFut,
+        quiet: Duration,
+    ) -> EventFut::Output
+    where
+        EventFut: Future,
+        GetNextFut: FnMut() -> EventFut,
+    {
+        let mut latest = next().await;
+        loop {
+            let event_fut = next();
+            let event = futures_lite::future::or(async { Some(event_fut.await) }, async {
+                sleep(quiet).await;
+                None
+            })
+            .await;
+            match event {
+                Some(event) => latest = event,
+                None => return latest,
+            }
+        }
+    }
+}
+
+pub mod manual {
+    use std::{future::Future, pin::Pin, task::Poll, time::Duration};
+
+    use pin_project::pin_project;
+
+    use crate::basics::{sleep, Sleep};
+
+    pub async fn debounce<EventFut, GetNextFut>(
+        mut next: GetNextFut,
+        quiet: Duration,
+    ) -> EventFut::Output
+    where
+        EventFut: Future,
+        GetNextFut: FnMut() -> EventFut,
+    {
+        Debounce {
+            quiet,
+            event_fut: next(),
+            next,
+            latest: None,
+            sleep: sleep(quiet),
+            done: false,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Debounce<EventFut: Future, GetNextFut> {
+        quiet: Duration,
+        next: GetNextFut,
+        #[pin]
+        event_fut: EventFut,
+        // `None` until the first event, before which there is nothing to
+        // resolve to and the sleep is never polled.
+        latest: Option<EventFut::Output>,
+        #[pin]
+        sleep: Sleep,
+        done: bool,
+    }
+
+    impl<EventFut, GetNextFut> Future for Debounce<EventFut, GetNextFut>
+    where
+        EventFut: Future,
+        GetNextFut: FnMut() -> EventFut,
+    {
+        type Output = EventFut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+
+            // several events may be ready back to back, and each must be
+            // taken before the quiet period can be judged.
+            while let Poll::Ready(event) = this.event_fut.as_mut().poll(cx) {
+                *this.latest = Some(event);
+                this.event_fut.set((this.next)());
+                this.sleep.as_mut().reset(*this.quiet);
+            }
+
+            if this.latest.is_none() || this.sleep.poll(cx).is_pending() {
+                return Poll::Pending;
+            }
+            *this.done = true;
+            Poll::Ready(this.latest.take().expect("checked above"))
+        }
+    }
+}
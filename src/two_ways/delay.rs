@@ -0,0 +1,84 @@
+//! Wait for a duration, then run a future.
+//!
+//! ```
+//! # use std::time::{Duration, Instant};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{basics::sleep, two_ways::delay::{auto, manual}};
+//!
+//! let delay = Duration::from_millis(20);
+//! let work = Duration::from_millis(10);
+//!
+//! let start = Instant::now();
+//! assert_eq!(block_on(auto::delay(delay, async { sleep(work).await; 1 })), 1);
+//! assert!(start.elapsed() >= delay + work);
+//!
+//! let start = Instant::now();
+//! assert_eq!(block_on(manual::delay(delay, async { sleep(work).await; 2 })), 2);
+//! assert!(start.elapsed() >= delay + work);
+//! ```
+
+pub mod auto {
+    use core::{future::Future, time::Duration};
+
+    use crate::basics::sleep;
+
+    pub async fn delay<Fut: Future>(dur: Duration, fut: Fut) -> Fut::Output {
+        sleep(dur).await;
+        fut.await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll, time::Duration};
+
+    use pin_project::pin_project;
+
+    use crate::basics::{sleep, Sleep};
+
+    pub async fn delay<Fut: Future>(dur: Duration, fut: Fut) -> Fut::Output {
+        Delay::Waiting {
+            sleep: sleep(dur),
+            fut: Some(fut),
+        }
+        .await
+    }
+
+    #[pin_project(project = DelayProj)]
+    enum Delay<Fut> {
+        // `fut` hasn't been polled yet, so it isn't pinned and may still be
+        // moved into `Running`. It sits in an `Option` rather than a
+        // `ManuallyDrop`: `Pin::set` drops the old `Waiting` when switching
+        // states, and an emptied `Option` is safe to drop where a taken
+        // `ManuallyDrop` would need a `PinnedDrop` impl to skip it.
+        Waiting {
+            #[pin]
+            sleep: Sleep,
+            fut: Option<Fut>,
+        },
+        Running {
+            #[pin]
+            fut: Fut,
+        },
+    }
+
+    impl<Fut: Future> Future for Delay<Fut> {
+        type Output = Fut::Output;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            loop {
+                match self.as_mut().project() {
+                    DelayProj::Waiting { sleep, fut } => {
+                        let Poll::Ready(()) = sleep.poll(cx) else {
+                            return Poll::Pending;
+                        };
+                        let fut = fut
+                            .take()
+                            .expect("`fut` is only taken when leaving `Waiting`");
+                        self.set(Delay::Running { fut });
+                    }
+                    DelayProj::Running { fut } => return fut.poll(cx),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,58 @@
+//! Unify two future types with the same output, without boxing.
+//!
+//! The two arms of an `if` must have the same type, and no two futures do.
+//! [`boxed`](super::boxed) erases both to a `Pin<Box<dyn Future>>`, paying for
+//! an allocation and a vtable call on every poll. An enum with one variant per
+//! arm does the same job on the stack: it is a future whenever both variants
+//! are, and polling it simply forwards to whichever variant it holds.
+//!
+//! ```
+//! # #[cfg(feature = "std")] {
+//! # use std::time::Duration;
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{
+//!     basics::{ready, sleep},
+//!     two_ways::either::manual::Either,
+//! };
+//!
+//! let wait = |slow: bool| {
+//!     if slow {
+//!         Either::Left(sleep(Duration::from_millis(10)))
+//!     } else {
+//!         Either::Right(ready(()))
+//!     }
+//! };
+//! block_on(wait(true));
+//! block_on(wait(false));
+//! # }
+//! ```
+//!
+//! There is no `auto` version. With `async`, the `if` moves inside a single
+//! `async` block instead, and the compiler builds the same enum into that
+//! block's state machine. That works well until the future has to be named,
+//! say as a struct field, which an `async` block's type can't be.
+//!
+//! The enum itself is the crate-wide [`Either`](crate::either::Either),
+//! re-exported here. Only the `Future` impl belongs to this module.
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    pub use crate::either::Either;
+    use crate::either::EitherProj;
+
+    impl<L, R> Future for Either<L, R>
+    where
+        L: Future,
+        R: Future<Output = L::Output>,
+    {
+        type Output = L::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            match self.project() {
+                EitherProj::Left(left) => left.poll(cx),
+                EitherProj::Right(right) => right.poll(cx),
+            }
+        }
+    }
+}
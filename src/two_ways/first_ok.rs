@@ -0,0 +1,129 @@
+//! Run two fallible futures concurrently, resolving to the first success. A
+//! failure doesn't end the race: only once both have failed does this resolve
+//! to an error, the one from whichever failed last.
+//!
+//! ```
+//! # use futures_lite::future::{block_on, pending, yield_now};
+//! use async_intuition::two_ways::first_ok::{auto, manual};
+//!
+//! let after = |yields, result: Result<&'static str, &'static str>| async move {
+//!     for _ in 0..yields {
+//!         yield_now().await;
+//!     }
+//!     result
+//! };
+//!
+//! // left succeeds while right never finishes.
+//! let never = || pending::<Result<&str, &str>>();
+//! assert_eq!(block_on(auto::first_ok(after(1, Ok("left")), never())), Ok("left"));
+//! assert_eq!(block_on(manual::first_ok(after(1, Ok("left")), never())), Ok("left"));
+//!
+//! // left fails first, then right succeeds.
+//! let (a, b) = (after(0, Err("left")), after(2, Ok("right")));
+//! assert_eq!(block_on(auto::first_ok(a, b)), Ok("right"));
+//! let (a, b) = (after(0, Err("left")), after(2, Ok("right")));
+//! assert_eq!(block_on(manual::first_ok(a, b)), Ok("right"));
+//!
+//! // both fail, and the last error wins.
+//! let (a, b) = (after(2, Err("left")), after(1, Err("right")));
+//! assert_eq!(block_on(auto::first_ok(a, b)), Err("left"));
+//! let (a, b) = (after(2, Err("left")), after(1, Err("right")));
+//! assert_eq!(block_on(manual::first_ok(a, b)), Err("left"));
+//! ```
+
+pub mod auto {
+    use core::{cell::Cell, future::Future};
+
+    use futures_lite::future::{or, pending};
+
+    pub async fn first_ok<T, E, A, B>(a: A, b: B) -> Result<T, E>
+    where
+        A: Future<Output = Result<T, E>>,
+        B: Future<Output = Result<T, E>>,
+    {
+        let one_failed = Cell::new(false);
+        // the first side to fail bows out of the race by never finishing,
+        // leaving the other side to decide the result.
+        let settle = |result| async {
+            match result {
+                Ok(t) => Ok(t),
+                Err(e) if one_failed.replace(true) => Err(e),
+                Err(_) => pending().await,
+            }
+        };
+        or(async { settle(a.await).await }, async {
+            settle(b.await).await
+        })
+        .await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn first_ok<T, E, A, B>(a: A, b: B) -> Result<T, E>
+    where
+        A: Future<Output = Result<T, E>>,
+        B: Future<Output = Result<T, E>>,
+    {
+        FirstOk {
+            a_live: true,
+            b_live: true,
+            a,
+            b,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct FirstOk<A, B> {
+        // a side stops being live once it fails, and is never polled again.
+        a_live: bool,
+        b_live: bool,
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+    }
+
+    impl<T, E, A, B> Future for FirstOk<A, B>
+    where
+        A: Future<Output = Result<T, E>>,
+        B: Future<Output = Result<T, E>>,
+    {
+        type Output = Result<T, E>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if !*this.a_live && !*this.b_live {
+                panic!("`async fn` resumed after completion");
+            }
+
+            if *this.a_live {
+                match this.a.poll(cx) {
+                    Poll::Ready(Ok(t)) => return Poll::Ready(Ok(t)),
+                    Poll::Ready(Err(e)) if !*this.b_live => {
+                        *this.a_live = false;
+                        return Poll::Ready(Err(e));
+                    }
+                    Poll::Ready(Err(_)) => *this.a_live = false,
+                    Poll::Pending => {}
+                }
+            }
+            if *this.b_live {
+                match this.b.poll(cx) {
+                    Poll::Ready(Ok(t)) => return Poll::Ready(Ok(t)),
+                    Poll::Ready(Err(e)) if !*this.a_live => {
+                        *this.b_live = false;
+                        return Poll::Ready(Err(e));
+                    }
+                    Poll::Ready(Err(_)) => *this.b_live = false,
+                    Poll::Pending => {}
+                }
+            }
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Run a future whose output is itself a future, then run that.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{testing::poll_counter, two_ways::flatten::{auto, manual}};
+//!
+//! let nested = || async {
+//!     poll_counter(2, ()).await;
+//!     poll_counter(3, 5)
+//! };
+//!
+//! assert_eq!(block_on(auto::flatten(nested())), 5);
+//! assert_eq!(block_on(manual::flatten(nested())), 5);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn flatten<Outer, Inner>(outer: Outer) -> Inner::Output
+    where
+        Outer: Future<Output = Inner>,
+        Inner: Future,
+    {
+        outer.await.await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn flatten<Outer, Inner>(outer: Outer) -> Inner::Output
+    where
+        Outer: Future<Output = Inner>,
+        Inner: Future,
+    {
+        Flatten::Outer { outer }.await
+    }
+
+    // only one arm is ever live, and the generated drop glue drops whichever
+    // it is in place, so a `PinnedDrop` impl would have nothing left to do.
+    #[pin_project(project = FlattenProj)]
+    enum Flatten<Outer, Inner> {
+        Outer {
+            #[pin]
+            outer: Outer,
+        },
+        Inner {
+            #[pin]
+            inner: Inner,
+        },
+    }
+
+    impl<Outer, Inner> Future for Flatten<Outer, Inner>
+    where
+        Outer: Future<Output = Inner>,
+        Inner: Future,
+    {
+        type Output = Inner::Output;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            loop {
+                match self.as_mut().project() {
+                    FlattenProj::Outer { outer } => {
+                        let Poll::Ready(inner) = outer.poll(cx) else {
+                            return Poll::Pending;
+                        };
+                        self.set(Flatten::Inner { inner });
+                    }
+                    FlattenProj::Inner { inner } => return inner.poll(cx),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,111 @@
+//! Fold an iterator into an accumulator, one future per item: `f` takes the
+//! accumulator and the next item, and returns a future resolving to the
+//! new accumulator. The steps run one after another, each starting once the
+//! one before has finished.
+//!
+//! Where [`scan`](super::scan) awaits the items and folds them synchronously,
+//! here the items are already to hand and it's the folding which is
+//! asynchronous.
+//!
+//! ```
+//! # use std::{pin::pin, task::Poll};
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::{testing::poll_once, two_ways::fold::{auto, manual}};
+//!
+//! let add_doubled = |acc: u32, n: u32| async move {
+//!     yield_now().await;
+//!     acc + 2 * n
+//! };
+//!
+//! assert_eq!(block_on(auto::fold(0..5, 0, add_doubled)), 20);
+//! assert_eq!(block_on(manual::fold(0..5, 0, add_doubled)), 20);
+//!
+//! // with nothing to fold, `init` comes straight back on the first poll.
+//! assert_eq!(poll_once(pin!(auto::fold(0..0, 7, add_doubled))), Poll::Ready(7));
+//! assert_eq!(poll_once(pin!(manual::fold(0..0, 7, add_doubled))), Poll::Ready(7));
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn fold<I, Acc, F, Fut>(iter: I, init: Acc, mut f: F) -> Acc
+    where
+        I: IntoIterator,
+        F: FnMut(Acc, I::Item) -> Fut,
+        Fut: Future<Output = Acc>,
+    {
+        let mut acc = init;
+        for item in iter {
+            acc = f(acc, item).await;
+        }
+        acc
+    }
+}
+
+pub mod manual {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn fold<I, Acc, F, Fut>(iter: I, init: Acc, f: F) -> Acc
+    where
+        I: IntoIterator,
+        F: FnMut(Acc, I::Item) -> Fut,
+        Fut: Future<Output = Acc>,
+    {
+        Fold {
+            iter: iter.into_iter(),
+            acc: Some(init),
+            f,
+            fut: None,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Fold<I, Acc, F, Fut> {
+        iter: I,
+        // handed to each step in turn, so `None` while a step is running,
+        // and once the final value has been returned.
+        acc: Option<Acc>,
+        f: F,
+        // the step in progress, created lazily from the next item.
+        #[pin]
+        fut: Option<Fut>,
+    }
+
+    impl<I, Acc, F, Fut> Future for Fold<I, Acc, F, Fut>
+    where
+        I: Iterator,
+        F: FnMut(Acc, I::Item) -> Fut,
+        Fut: Future<Output = Acc>,
+    {
+        type Output = Acc;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            // a step which finishes goes straight on to the next one, since
+            // nothing would wake us if we returned `Pending` in between.
+            loop {
+                if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+                    let acc = ready!(fut.poll(cx));
+                    this.fut.set(None);
+                    *this.acc = Some(acc);
+                }
+
+                let acc = this
+                    .acc
+                    .take()
+                    .expect("`async fn` resumed after completion");
+                match this.iter.next() {
+                    Some(item) => this.fut.set(Some((this.f)(acc, item))),
+                    None => return Poll::Ready(acc),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,126 @@
+//! Wrap a future so that polling it again after it has completed is harmless.
+//!
+//! The [`Future`] contract leaves polling after completion unspecified, and
+//! the futures `async fn` produces choose to panic with "`async fn` resumed
+//! after completion". That's a fine way to catch bugs, but code which polls
+//! a set of futures in a loop (`select`, for instance) then has to keep track
+//! of which ones are finished itself.
+//!
+//! A fused future keeps track instead. There are two reasonable behaviors
+//! once it has completed: return `Pending` forever, as if it were never going
+//! to finish again, or hand back some marker value such as `Option::None`.
+//! [`Fuse`](manual::Fuse) does the former, which keeps its output type
+//! unchanged so it can stand in for the inner future anywhere. Since
+//! returning `Pending` here never arranges a wakeup, a task awaiting nothing
+//! but a completed `Fuse` will sleep forever.
+//!
+//! There is no `auto` version, as wrapping this in an `async fn` would put
+//! the panic right back. [`Fuse`](manual::Fuse) is returned as is.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! use async_intuition::two_ways::fuse::manual;
+//!
+//! let mut fut = pin!(manual::fuse(async { 5 }));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(5));
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+//! ```
+//!
+//! Returning `Pending` forever is safe, but whoever is polling still can't
+//! tell a finished `Fuse` from one which is merely waiting. [`FusedFuture`]
+//! lets them ask, so that finished futures can be skipped entirely:
+//!
+//! ```
+//! # use std::{pin::pin, task::Poll};
+//! use async_intuition::{
+//!     testing::{noop_context, poll_counter},
+//!     two_ways::fuse::{manual, poll_if_not_terminated, FusedFuture},
+//! };
+//!
+//! let mut fut = pin!(manual::fuse(poll_counter(1, 5)));
+//! let mut cx = noop_context();
+//! assert_eq!(poll_if_not_terminated(fut.as_mut(), &mut cx), Some(Poll::Pending));
+//! assert!(!fut.is_terminated());
+//! assert_eq!(poll_if_not_terminated(fut.as_mut(), &mut cx), Some(Poll::Ready(5)));
+//! assert!(fut.is_terminated());
+//! assert_eq!(poll_if_not_terminated(fut.as_mut(), &mut cx), None);
+//! ```
+
+use core::{
+    future::Future,
+    pin::Pin,
+    task::{Context, Poll},
+};
+
+/// A future which knows when it has completed, and so must not be polled
+/// again.
+pub trait FusedFuture: Future {
+    fn is_terminated(&self) -> bool;
+}
+
+/// Poll `fut`, unless it has already completed, in which case `None`.
+pub fn poll_if_not_terminated<F: FusedFuture>(
+    fut: Pin<&mut F>,
+    cx: &mut Context<'_>,
+) -> Option<Poll<F::Output>> {
+    if fut.is_terminated() {
+        None
+    } else {
+        Some(fut.poll(cx))
+    }
+}
+
+pub mod manual {
+    use core::{
+        fmt,
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use super::FusedFuture;
+
+    pub fn fuse<Fut: Future>(fut: Fut) -> Fuse<Fut> {
+        Fuse { inner: Some(fut) }
+    }
+
+    #[pin_project]
+    pub struct Fuse<Fut> {
+        // `None` once the inner future has completed.
+        #[pin]
+        inner: Option<Fut>,
+    }
+
+    impl<Fut> fmt::Debug for Fuse<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("Fuse")
+                .field("terminated", &self.inner.is_none())
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<Fut: Future> Future for Fuse<Fut> {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            let Some(inner) = this.inner.as_mut().as_pin_mut() else {
+                return Poll::Pending;
+            };
+            let output = ready!(inner.poll(cx));
+            // drop the finished future in place, so nothing can poll it again.
+            this.inner.set(None);
+            Poll::Ready(output)
+        }
+    }
+
+    impl<Fut: Future> FusedFuture for Fuse<Fut> {
+        fn is_terminated(&self) -> bool {
+            self.inner.is_none()
+        }
+    }
+}
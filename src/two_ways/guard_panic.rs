@@ -0,0 +1,147 @@
+//! Catch a panic from inside a future's `poll`, turning it into an error
+//! instead of unwinding through whoever polled it.
+//!
+//! A panic can leave the inner future halfway through a state change, so
+//! once one has been caught, the future is never polled again: it is
+//! poisoned, and every later poll of the manual version resolves to a
+//! [`Poisoned`] error instead. The inner future stays pinned where it is
+//! until the wrapper is dropped, broken state and all.
+//!
+//! `catch_unwind` insists on [`UnwindSafe`](std::panic::UnwindSafe), which a
+//! `Pin<&mut Fut>` generally isn't. Poisoning is what makes it fine to
+//! override that with `AssertUnwindSafe`: nothing ever looks at the future's
+//! state after the panic.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::Poll};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{
+//!     testing::poll_once,
+//!     two_ways::guard_panic::{auto, manual, Poisoned},
+//! };
+//!
+//! // pending on the first poll, then panics on the second.
+//! let flaky = || {
+//!     let mut polls = 0;
+//!     std::future::poll_fn(move |cx| {
+//!         polls += 1;
+//!         if polls == 2 {
+//!             panic!("second poll");
+//!         }
+//!         cx.waker().wake_by_ref();
+//!         Poll::<u8>::Pending
+//!     })
+//! };
+//!
+//! let payload = block_on(auto::guard_panic(flaky())).unwrap_err();
+//! assert_eq!(payload.downcast_ref::<&str>(), Some(&"second poll"));
+//!
+//! let mut fut = pin!(manual::guard_panic(flaky()));
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! let Poll::Ready(Err(payload)) = poll_once(fut.as_mut()) else {
+//!     panic!("the panic should have been caught");
+//! };
+//! assert_eq!(payload.downcast_ref::<&str>(), Some(&"second poll"));
+//! let Poll::Ready(Err(payload)) = poll_once(fut.as_mut()) else {
+//!     panic!("a poisoned future should resolve straight away");
+//! };
+//! assert!(payload.is::<Poisoned>());
+//!
+//! assert_eq!(block_on(manual::guard_panic(async { 1 })).unwrap(), 1);
+//! ```
+
+use std::fmt;
+
+/// What a poisoned [`manual::GuardPanic`] resolves to, boxed up the same way
+/// as a caught panic's payload.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Poisoned;
+
+impl fmt::Display for Poisoned {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("future polled after panicking")
+    }
+}
+
+impl std::error::Error for Poisoned {}
+
+pub mod auto {
+    use std::{any::Any, future::Future, panic::AssertUnwindSafe};
+
+    use futures_lite::FutureExt;
+
+    pub async fn guard_panic<Fut: Future>(fut: Fut) -> Result<Fut::Output, Box<dyn Any + Send>> {
+        AssertUnwindSafe(fut).catch_unwind().await
+    }
+}
+
+pub mod manual {
+    use std::{
+        any::Any,
+        fmt,
+        future::Future,
+        panic::{self, AssertUnwindSafe},
+        pin::Pin,
+        task::Poll,
+    };
+
+    use pin_project::pin_project;
+
+    use super::Poisoned;
+
+    pub fn guard_panic<Fut: Future>(fut: Fut) -> GuardPanic<Fut> {
+        GuardPanic {
+            fut,
+            state: State::Running,
+        }
+    }
+
+    #[derive(Debug)]
+    enum State {
+        Running,
+        Done,
+        // a poll panicked, so the future can't be trusted any more.
+        Poisoned,
+    }
+
+    #[pin_project]
+    pub struct GuardPanic<Fut> {
+        #[pin]
+        fut: Fut,
+        state: State,
+    }
+
+    impl<Fut> fmt::Debug for GuardPanic<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("GuardPanic")
+                .field("state", &self.state)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<Fut: Future> Future for GuardPanic<Fut> {
+        type Output = Result<Fut::Output, Box<dyn Any + Send>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            match this.state {
+                State::Running => {}
+                State::Done => panic!("`async fn` resumed after completion"),
+                State::Poisoned => return Poll::Ready(Err(Box::new(Poisoned))),
+            }
+
+            let fut = this.fut;
+            match panic::catch_unwind(AssertUnwindSafe(|| fut.poll(cx))) {
+                Ok(Poll::Pending) => Poll::Pending,
+                Ok(Poll::Ready(out)) => {
+                    *this.state = State::Done;
+                    Poll::Ready(Ok(out))
+                }
+                Err(payload) => {
+                    *this.state = State::Poisoned;
+                    Poll::Ready(Err(payload))
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,76 @@
+//! Run a side effect on a future's output by reference, then pass the output
+//! along unchanged.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::inspect::{auto, manual};
+//!
+//! let seen = Cell::new(0);
+//! let record = |n: &u32| seen.set(*n);
+//!
+//! assert_eq!(block_on(auto::inspect(async { 3 }, record)), 3);
+//! assert_eq!(seen.get(), 3);
+//! assert_eq!(block_on(manual::inspect(async { 4 }, record)), 4);
+//! assert_eq!(seen.get(), 4);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn inspect<Fut, F>(fut: Fut, f: F) -> Fut::Output
+    where
+        Fut: Future,
+        F: FnOnce(&Fut::Output),
+    {
+        let out = fut.await;
+        f(&out);
+        out
+    }
+}
+
+pub mod manual {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn inspect<Fut, F>(fut: Fut, f: F) -> Fut::Output
+    where
+        Fut: Future,
+        F: FnOnce(&Fut::Output),
+    {
+        Inspect { fut, f: Some(f) }.await
+    }
+
+    #[pin_project]
+    struct Inspect<Fut, F> {
+        #[pin]
+        fut: Fut,
+        // `None` once the closure has been called, which doubles as the
+        // "completed" state.
+        f: Option<F>,
+    }
+
+    impl<Fut, F> Future for Inspect<Fut, F>
+    where
+        Fut: Future,
+        F: FnOnce(&Fut::Output),
+    {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if this.f.is_none() {
+                panic!("`async fn` resumed after completion");
+            }
+            let output = ready!(this.fut.poll(cx));
+            let f = this.f.take().expect("checked above");
+            f(&output);
+            Poll::Ready(output)
+        }
+    }
+}
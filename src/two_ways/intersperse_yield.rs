@@ -0,0 +1,104 @@
+//! Run a future, yielding back to the executor between each pair of its polls.
+//! Every time the inner future returns `Pending`, the next poll goes to a
+//! [`yield_now`](fn@crate::basics::yield_now) before the inner future is
+//! touched again. An inner future which keeps waking itself straight back up (like
+//! one making steady progress through a lot of work) then lets the other
+//! tasks on its executor run in between, rather than hogging the queue.
+//!
+//! There is no `auto` version, for the same reason as with
+//! [`count_polls`](super::count_polls): an `.await` hides the inner future's
+//! individual `Pending`s, and those are what the yields go between.
+//!
+//! Each yield costs one extra `Pending` and one extra wakeup on top of the
+//! inner future's own:
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, sync::Arc, task::Context};
+//! use async_intuition::{
+//!     testing::{poll_counter, RecordingWaker},
+//!     two_ways::intersperse_yield::manual::intersperse_yield,
+//! };
+//!
+//! // polls `fut` to completion, returning how many polls that took.
+//! fn polls_to_finish(fut: impl Future, recorder: &Arc<RecordingWaker>) -> usize {
+//!     let waker = recorder.waker();
+//!     let mut cx = Context::from_waker(&waker);
+//!     let mut fut = pin!(fut);
+//!     let mut polls = 1;
+//!     while fut.as_mut().poll(&mut cx).is_pending() {
+//!         polls += 1;
+//!     }
+//!     polls
+//! }
+//!
+//! let bare = RecordingWaker::new();
+//! assert_eq!(polls_to_finish(poll_counter(3, ()), &bare), 4);
+//! assert_eq!(bare.wakes_by_ref(), 3);
+//!
+//! let interspersed = RecordingWaker::new();
+//! assert_eq!(polls_to_finish(intersperse_yield(poll_counter(3, ())), &interspersed), 7);
+//! // three wakeups from the inner future, and three from yielding.
+//! assert_eq!(interspersed.wakes_by_ref(), 6);
+//! ```
+
+pub mod manual {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use crate::basics::{yield_now, YieldNow};
+
+    pub async fn intersperse_yield<Fut: Future>(fut: Fut) -> Fut::Output {
+        IntersperseYield {
+            fut,
+            yield_now: None,
+            done: false,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct IntersperseYield<Fut> {
+        #[pin]
+        fut: Fut,
+        // `Some` from the inner future returning `Pending` until the yield
+        // after it has completed.
+        #[pin]
+        yield_now: Option<YieldNow>,
+        done: bool,
+    }
+
+    impl<Fut: Future> Future for IntersperseYield<Fut> {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+
+            if let Some(yield_now) = this.yield_now.as_mut().as_pin_mut() {
+                ready!(yield_now.poll(cx));
+                this.yield_now.set(None);
+            }
+
+            match this.fut.poll(cx) {
+                Poll::Ready(out) => {
+                    *this.done = true;
+                    Poll::Ready(out)
+                }
+                Poll::Pending => {
+                    // the yield isn't polled until the inner future's own
+                    // wakeup comes in, so an inner future with nothing to do
+                    // isn't turned into a busy loop.
+                    this.yield_now.set(Some(yield_now()));
+                    Poll::Pending
+                }
+            }
+        }
+    }
+}
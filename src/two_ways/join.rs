@@ -0,0 +1,314 @@
+//! Run two futures concurrently, resolving to both outputs once each has
+//! completed.
+//!
+//! ```
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::join::{auto, manual};
+//!
+//! let slow = || async {
+//!     yield_now().await;
+//!     "slow"
+//! };
+//!
+//! assert_eq!(block_on(auto::join(slow(), async { 1 })), ("slow", 1));
+//! assert_eq!(block_on(manual::join(slow(), async { 1 })), ("slow", 1));
+//! ```
+//!
+//! If the join is dropped after only one side has finished, the output already
+//! stored for that side must still be dropped:
+//!
+//! ```
+//! # use std::{future::Future, rc::Rc, task::{Context, Waker}};
+//! # use futures_lite::future::pending;
+//! use async_intuition::two_ways::join::manual;
+//!
+//! let out = Rc::new(());
+//! let mut fut = Box::pin(manual::join(async { out.clone() }, pending::<()>()));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert!(fut.as_mut().poll(&mut cx).is_pending());
+//! assert_eq!(Rc::strong_count(&out), 2);
+//! drop(fut);
+//! assert_eq!(Rc::strong_count(&out), 1);
+//! ```
+//!
+//! The outputs come back in argument order, not completion order: here `b`
+//! finishes two polls ahead of `a`, yet `a`'s output is still first.
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{
+//!     testing::poll_counter,
+//!     two_ways::join::{auto, manual, manual_opt},
+//! };
+//!
+//! let finished = RefCell::new(Vec::new());
+//! // resolves to `name` after `n` pending polls, noting when it finished.
+//! let after = |n, name| {
+//!     let finished = &finished;
+//!     async move {
+//!         let name = poll_counter(n, name).await;
+//!         finished.borrow_mut().push(name);
+//!         name
+//!     }
+//! };
+//!
+//! assert_eq!(block_on(auto::join(after(3, "a"), after(1, "b"))), ("a", "b"));
+//! assert_eq!(finished.take(), ["b", "a"]);
+//! assert_eq!(block_on(manual::join(after(3, "a"), after(1, "b"))), ("a", "b"));
+//! assert_eq!(finished.take(), ["b", "a"]);
+//! assert_eq!(block_on(manual_opt::join(after(3, "a"), after(1, "b"))), ("a", "b"));
+//! assert_eq!(finished.take(), ["b", "a"]);
+//! ```
+//!
+//! Every output written into a slot is dropped exactly once, whichever state
+//! the join is dropped in. Being all about `MaybeUninit`, this is worth
+//! running under `cargo +nightly miri test`:
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! # use futures_lite::future::yield_now;
+//! use async_intuition::{testing::poll_once, two_ways::join::manual};
+//!
+//! thread_local! {
+//!     static DROPPED: RefCell<Vec<&'static str>> = RefCell::default();
+//! }
+//!
+//! struct Payload(&'static str);
+//!
+//! impl Drop for Payload {
+//!     fn drop(&mut self) {
+//!         DROPPED.with_borrow_mut(|dropped| dropped.push(self.0));
+//!     }
+//! }
+//!
+//! let take_dropped = || DROPPED.take();
+//! // resolves to a payload on the given poll.
+//! let ready_on = |poll: usize, name| async move {
+//!     for _ in 1..poll {
+//!         yield_now().await;
+//!     }
+//!     Payload(name)
+//! };
+//!
+//! // dropped with neither, just `a`, or just `b` stored.
+//! for (a_poll, b_poll, stored) in [(2, 2, vec![]), (1, 2, vec!["a"]), (2, 1, vec!["b"])] {
+//!     let mut join = Box::pin(manual::join(ready_on(a_poll, "a"), ready_on(b_poll, "b")));
+//!     assert!(poll_once(join.as_mut()).is_pending());
+//!     assert!(take_dropped().is_empty());
+//!     drop(join);
+//!     assert_eq!(take_dropped(), stored);
+//! }
+//!
+//! // done: the outputs belong to the caller now.
+//! let mut join = Box::pin(manual::join(ready_on(1, "a"), ready_on(1, "b")));
+//! let outputs = poll_once(join.as_mut());
+//! drop(join);
+//! assert!(take_dropped().is_empty());
+//! drop(outputs);
+//! assert_eq!(take_dropped(), ["a", "b"]);
+//! ```
+//!
+//! Like [`zip`](super::zip), both versions remember which side has finished
+//! and only ever poll the side that hasn't, however many spurious wakeups
+//! arrive in between. Where `zip` keeps an `Option` per output, [`manual`]
+//! packs that knowledge into one state and leaves the outputs in
+//! `MaybeUninit` slots, so there's no discriminant per output to store or
+//! check. Either way, though, each side keeps room for its future *and* its
+//! output side by side, even though it never needs both at once.
+//!
+//! [`manual_opt`] wraps each side in a [`MaybeDone`] instead, so the output
+//! takes over the memory the finished future used to occupy. That also drops
+//! each future as soon as it completes, rather than when the whole join is
+//! dropped:
+//!
+//! ```
+//! # use std::{future::Future, mem::size_of_val, pin::{pin, Pin}, task::{Context, Poll}};
+//! # use futures_lite::future::{pending, yield_now};
+//! use async_intuition::{testing::poll_once, two_ways::join::{manual, manual_opt}};
+//!
+//! struct ReadyOnce(bool);
+//!
+//! impl Future for ReadyOnce {
+//!     type Output = u8;
+//!
+//!     fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<u8> {
+//!         assert!(!self.0, "polled after returning `Ready`");
+//!         self.0 = true;
+//!         Poll::Ready(1)
+//!     }
+//! }
+//!
+//! // spurious wakeups: `b` keeps the join pending, and `a` must be left alone.
+//! let mut join = pin!(manual_opt::join(ReadyOnce(false), pending::<()>()));
+//! for _ in 0..3 {
+//!     assert!(poll_once(join.as_mut()).is_pending());
+//! }
+//!
+//! let big = || async {
+//!     let buf = [0u8; 1024];
+//!     yield_now().await;
+//!     buf
+//! };
+//! let opt = size_of_val(&manual_opt::join(big(), big()));
+//! assert!(opt + 2 * 1024 <= size_of_val(&manual::join(big(), big())));
+//! ```
+//!
+//! [`MaybeDone`]: super::maybe_done::manual::MaybeDone
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+        futures_lite::future::zip(a, b).await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, mem::MaybeUninit, pin::Pin, task::Poll};
+
+    use pin_project::{pin_project, pinned_drop};
+
+    pub async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+        Join {
+            state: State::default(),
+            a,
+            b,
+            a_out: MaybeUninit::uninit(),
+            b_out: MaybeUninit::uninit(),
+        }
+        .await
+    }
+
+    #[derive(Clone, Copy)]
+    enum State {
+        // each flag records whether the matching output slot is initialized.
+        Joining { a_done: bool, b_done: bool },
+        Done,
+    }
+
+    impl Default for State {
+        fn default() -> Self {
+            State::Joining {
+                a_done: false,
+                b_done: false,
+            }
+        }
+    }
+
+    #[pin_project(PinnedDrop)]
+    struct Join<A: Future, B: Future> {
+        state: State,
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+        a_out: MaybeUninit<A::Output>,
+        b_out: MaybeUninit<B::Output>,
+    }
+
+    impl<A: Future, B: Future> Future for Join<A, B> {
+        type Output = (A::Output, B::Output);
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let State::Joining {
+                mut a_done,
+                mut b_done,
+            } = *this.state
+            else {
+                panic!("`async fn` resumed after completion");
+            };
+
+            // a finished future must not be polled again, so only poll
+            // whichever side has yet to produce its output.
+            if !a_done {
+                if let Poll::Ready(out) = this.a.poll(cx) {
+                    this.a_out.write(out);
+                    a_done = true;
+                }
+            }
+            if !b_done {
+                if let Poll::Ready(out) = this.b.poll(cx) {
+                    this.b_out.write(out);
+                    b_done = true;
+                }
+            }
+
+            if a_done && b_done {
+                *this.state = State::Done;
+                // SAFETY: both slots were written (now or on an earlier poll),
+                // and `State::Done` hands ownership of them to the caller.
+                let outputs =
+                    unsafe { (this.a_out.assume_init_read(), this.b_out.assume_init_read()) };
+                Poll::Ready(outputs)
+            } else {
+                *this.state = State::Joining { a_done, b_done };
+                Poll::Pending
+            }
+        }
+    }
+
+    #[pinned_drop]
+    impl<A: Future, B: Future> PinnedDrop for Join<A, B> {
+        fn drop(self: Pin<&mut Self>) {
+            let this = self.project();
+            // dropped before both sides finished: one output may already be
+            // stored and would otherwise leak.
+            if let State::Joining { a_done, b_done } = *this.state {
+                if a_done {
+                    // SAFETY: `a_done` is only set after `a_out` is written.
+                    unsafe { this.a_out.assume_init_drop() };
+                }
+                if b_done {
+                    // SAFETY: `b_done` is only set after `b_out` is written.
+                    unsafe { this.b_out.assume_init_drop() };
+                }
+            }
+        }
+    }
+}
+
+pub mod manual_opt {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    use crate::two_ways::maybe_done::manual::{maybe_done, MaybeDone};
+
+    pub async fn join<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+        Join {
+            a: maybe_done(a),
+            b: maybe_done(b),
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Join<A: Future, B: Future> {
+        #[pin]
+        a: MaybeDone<A>,
+        #[pin]
+        b: MaybeDone<B>,
+    }
+
+    impl<A: Future, B: Future> Future for Join<A, B> {
+        type Output = (A::Output, B::Output);
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            // a finished `MaybeDone` is ready straight away without touching
+            // the future it used to hold, so this only polls whichever side
+            // has yet to finish.
+            let a_done = this.a.as_mut().poll(cx).is_ready();
+            let b_done = this.b.as_mut().poll(cx).is_ready();
+            if !(a_done && b_done) {
+                return Poll::Pending;
+            }
+
+            let a = this.a.take_output().expect("`a` is done");
+            let b = this.b.take_output().expect("`b` is done");
+            Poll::Ready((a, b))
+        }
+    }
+}
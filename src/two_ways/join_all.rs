@@ -0,0 +1,85 @@
+//! Run any number of futures of the same type concurrently, resolving to all
+//! of their outputs in the order the futures were given.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{testing::poll_counter, two_ways::join_all::{auto, manual}};
+//!
+//! // each resolves to how many polls it stays pending for.
+//! let counters = || [3, 2, 5, 6, 4].map(|n| poll_counter(n, n));
+//!
+//! assert_eq!(block_on(auto::join_all(counters().into())), [3, 2, 5, 6, 4]);
+//! assert_eq!(block_on(manual::join_all(counters().into())), [3, 2, 5, 6, 4]);
+//!
+//! let nothing: Vec<std::future::Ready<()>> = Vec::new();
+//! assert_eq!(block_on(manual::join_all(nothing)), []);
+//! ```
+
+pub mod auto {
+    use alloc::vec::Vec;
+    use core::future::Future;
+
+    // each future only starts running once it is awaited, so this takes as
+    // long as all of them put together rather than as long as the slowest.
+    pub async fn join_all<Fut: Future>(futs: Vec<Fut>) -> Vec<Fut::Output> {
+        let mut outputs = Vec::with_capacity(futs.len());
+        for fut in futs {
+            outputs.push(fut.await);
+        }
+        outputs
+    }
+}
+
+pub mod manual {
+    use alloc::{boxed::Box, vec::Vec};
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use crate::two_ways::maybe_done::manual::{maybe_done, MaybeDone};
+
+    pub async fn join_all<Fut: Future>(futs: Vec<Fut>) -> Vec<Fut::Output> {
+        JoinAll {
+            elems: futs.into_iter().map(maybe_done).collect::<Box<_>>().into(),
+        }
+        .await
+    }
+
+    // the futures can't be pinned where they are, since a `Vec` may move its
+    // elements when it grows. A boxed slice never grows, so once it is pinned
+    // its elements stay put for good. As the `Pin` lives in the box rather
+    // than around `JoinAll`, `JoinAll` itself can be moved freely.
+    struct JoinAll<Fut: Future> {
+        elems: Pin<Box<[MaybeDone<Fut>]>>,
+    }
+
+    impl<Fut: Future> JoinAll<Fut> {
+        fn elems(&mut self) -> impl Iterator<Item = Pin<&mut MaybeDone<Fut>>> {
+            // SAFETY: each element is pinned in place along with the slice,
+            // and none of them is ever moved out of it.
+            let elems = unsafe { self.elems.as_mut().get_unchecked_mut() };
+            elems
+                .iter_mut()
+                .map(|elem| unsafe { Pin::new_unchecked(elem) })
+        }
+    }
+
+    impl<Fut: Future> Future for JoinAll<Fut> {
+        type Output = Vec<Fut::Output>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            let mut all_done = true;
+            for elem in this.elems() {
+                // a completed `MaybeDone` just returns `Ready` again without
+                // touching its future.
+                all_done &= elem.poll(cx).is_ready();
+            }
+
+            if all_done {
+                let outputs = this.elems().map(|elem| elem.take_output().unwrap());
+                Poll::Ready(outputs.collect())
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,82 @@
+//! Put off creating a future until the first time it is polled.
+//!
+//! ```
+//! # use std::{cell::Cell, future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! use async_intuition::two_ways::lazy::{auto, manual};
+//!
+//! let created = Cell::new(0);
+//! let make = || {
+//!     created.set(created.get() + 1);
+//!     async { 5 }
+//! };
+//!
+//! let mut cx = Context::from_waker(Waker::noop());
+//! let mut fut = pin!(auto::lazy(make));
+//! assert_eq!(created.get(), 0);
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(5));
+//! assert_eq!(created.get(), 1);
+//!
+//! let mut fut = pin!(manual::lazy(make));
+//! assert_eq!(created.get(), 1);
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(5));
+//! assert_eq!(created.get(), 2);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    // the body of an `async fn` doesn't start until the first poll, so this
+    // is lazy without having to try.
+    pub async fn lazy<F, Fut>(f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future,
+    {
+        f().await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn lazy<F, Fut>(f: F) -> Fut::Output
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future,
+    {
+        Lazy::Init { f: Some(f) }.await
+    }
+
+    #[pin_project(project = LazyProj)]
+    enum Lazy<F, Fut> {
+        Init {
+            f: Option<F>,
+        },
+        Running {
+            #[pin]
+            fut: Fut,
+        },
+    }
+
+    impl<F, Fut> Future for Lazy<F, Fut>
+    where
+        F: FnOnce() -> Fut,
+        Fut: Future,
+    {
+        type Output = Fut::Output;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            loop {
+                match self.as_mut().project() {
+                    LazyProj::Init { f } => {
+                        let f = f.take().expect("`f` is only taken when leaving `Init`");
+                        self.set(Lazy::Running { fut: f() });
+                    }
+                    LazyProj::Running { fut } => return fut.poll(cx),
+                }
+            }
+        }
+    }
+}
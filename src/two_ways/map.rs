@@ -0,0 +1,67 @@
+//! Transform the output of a future with a closure.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::map::{auto, manual};
+//!
+//! let to_string = |n: u32| n.to_string();
+//! assert_eq!(block_on(auto::map(async { 7 }, to_string)), "7");
+//! assert_eq!(block_on(manual::map(async { 7 }, to_string)), "7");
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn map<Fut, F, T>(fut: Fut, f: F) -> T
+    where
+        Fut: Future,
+        F: FnOnce(Fut::Output) -> T,
+    {
+        f(fut.await)
+    }
+}
+
+pub mod manual {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn map<Fut, F, T>(fut: Fut, f: F) -> T
+    where
+        Fut: Future,
+        F: FnOnce(Fut::Output) -> T,
+    {
+        Map { fut, f: Some(f) }.await
+    }
+
+    #[pin_project]
+    struct Map<Fut, F> {
+        #[pin]
+        fut: Fut,
+        // `None` once the closure has been called, which doubles as the
+        // "completed" state.
+        f: Option<F>,
+    }
+
+    impl<Fut, F, T> Future for Map<Fut, F>
+    where
+        Fut: Future,
+        F: FnOnce(Fut::Output) -> T,
+    {
+        type Output = T;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if this.f.is_none() {
+                panic!("`async fn` resumed after completion");
+            }
+            let output = ready!(this.fut.poll(cx));
+            let f = this.f.take().expect("checked above");
+            Poll::Ready(f(output))
+        }
+    }
+}
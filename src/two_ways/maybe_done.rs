@@ -0,0 +1,115 @@
+//! Drive a future to completion, then hold on to its output until someone
+//! asks for it. This is the building block inside most `join`
+//! implementations: each child is wrapped in a [`MaybeDone`](manual::MaybeDone)
+//! which can be polled again and again, and the outputs are collected once
+//! every child is done.
+//!
+//! There is no `auto` version, since the output has to stay reachable after
+//! the future completes.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::{Context, Poll, Waker}};
+//! use async_intuition::two_ways::{
+//!     fuse::FusedFuture,
+//!     maybe_done::manual::{maybe_done, MaybeDone},
+//! };
+//!
+//! let mut fut = pin!(maybe_done(async { 5 }));
+//! let mut cx = Context::from_waker(Waker::noop());
+//! assert!(matches!(*fut, MaybeDone::Future { .. }));
+//! assert_eq!(fut.as_mut().take_output(), None);
+//!
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(()));
+//! assert!(matches!(*fut, MaybeDone::Done { output: 5 }));
+//! // polling a finished `MaybeDone` is fine, unlike the future inside it.
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(()));
+//!
+//! assert!(!fut.is_terminated());
+//!
+//! assert_eq!(fut.as_mut().take_output(), Some(5));
+//! assert!(matches!(*fut, MaybeDone::Gone));
+//! assert!(fut.is_terminated());
+//! assert_eq!(fut.as_mut().take_output(), None);
+//! ```
+
+pub mod manual {
+    use core::{
+        fmt,
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    use crate::two_ways::fuse::FusedFuture;
+
+    pub fn maybe_done<Fut: Future>(fut: Fut) -> MaybeDone<Fut> {
+        MaybeDone::Future { fut }
+    }
+
+    #[pin_project(project = MaybeDoneProj, project_replace = MaybeDoneProjReplace)]
+    pub enum MaybeDone<Fut: Future> {
+        Future {
+            #[pin]
+            fut: Fut,
+        },
+        Done {
+            output: Fut::Output,
+        },
+        // the output has been taken.
+        Gone,
+    }
+
+    // names the state, without needing the future or its output to be
+    // `Debug`.
+    impl<Fut: Future> fmt::Debug for MaybeDone<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.write_str(match self {
+                MaybeDone::Future { .. } => "MaybeDone::Future",
+                MaybeDone::Done { .. } => "MaybeDone::Done",
+                MaybeDone::Gone => "MaybeDone::Gone",
+            })
+        }
+    }
+
+    impl<Fut: Future> MaybeDone<Fut> {
+        /// Move the output out, if the future has completed and the output
+        /// hasn't been taken already.
+        pub fn take_output(self: Pin<&mut Self>) -> Option<Fut::Output> {
+            let MaybeDone::Done { .. } = *self else {
+                return None;
+            };
+            // `project_replace` hands back the unpinned fields by value,
+            // which is how `output` gets out from behind the `Pin`.
+            match self.project_replace(MaybeDone::Gone) {
+                MaybeDoneProjReplace::Done { output } => Some(output),
+                _ => unreachable!("checked to be `Done` above"),
+            }
+        }
+    }
+
+    impl<Fut: Future> Future for MaybeDone<Fut> {
+        type Output = ();
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            match self.as_mut().project() {
+                MaybeDoneProj::Future { fut } => {
+                    let output = ready!(fut.poll(cx));
+                    self.set(MaybeDone::Done { output });
+                }
+                MaybeDoneProj::Done { .. } => {}
+                MaybeDoneProj::Gone => panic!("`MaybeDone` polled after its output was taken"),
+            }
+            Poll::Ready(())
+        }
+    }
+
+    // a finished `MaybeDone` can be polled any number of times, up until its
+    // output has been taken.
+    impl<Fut: Future> FusedFuture for MaybeDone<Fut> {
+        fn is_terminated(&self) -> bool {
+            matches!(self, MaybeDone::Gone)
+        }
+    }
+}
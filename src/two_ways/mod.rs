@@ -22,6 +22,69 @@
 //!     }
 //! }
 //! ```
+//!
+//! `benches/a_then_b.rs` puts numbers on the "same code" claim for
+//! [`a_then_b`].
 
 pub mod a_then_b;
+#[cfg(feature = "std")]
+pub mod abortable;
+pub mod and_then;
+pub mod batch;
+pub mod boxed;
+pub mod catch_completion;
+pub mod chain3;
+pub mod count_polls;
+#[cfg(feature = "std")]
+pub mod debounce;
+#[cfg(feature = "std")]
+pub mod delay;
+pub mod either;
+pub mod first_ok;
+pub mod flatten;
+pub mod fold;
+pub mod fuse;
+#[cfg(feature = "std")]
+pub mod guard_panic;
+pub mod inspect;
+pub mod intersperse_yield;
+pub mod join;
+pub mod join_all;
+pub mod lazy;
+pub mod map;
+pub mod maybe_done;
+pub mod on_drop;
+pub mod peek_ready;
+pub mod pending_once;
+pub mod poll_budget;
+pub mod poll_fn;
+pub mod poll_immediate;
+pub mod race;
+pub mod race_ok;
+pub mod repeat;
+pub mod retry;
+pub mod scan;
+pub mod select;
+pub mod select_all;
+pub mod select_biased;
+pub mod select_ok;
+pub mod sequence;
+#[cfg(feature = "std")]
+pub mod shared;
+#[cfg(feature = "std")]
+pub mod shared_state;
+pub mod then;
+#[cfg(feature = "std")]
+pub mod throttle;
+#[cfg(feature = "std")]
+pub mod timeout;
+#[cfg(feature = "std")]
+pub mod timeout_at;
+pub mod try_join;
+pub mod tuple_join;
+pub mod unfold;
 pub mod until_equals;
+pub mod with_context;
+#[cfg(feature = "std")]
+pub mod with_deadline;
+pub mod zip;
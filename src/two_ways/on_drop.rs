@@ -0,0 +1,103 @@
+//! Run a callback when a future goes away, whether it ran to completion or
+//! was dropped partway through.
+//!
+//! Neither version runs the callback when the inner future completes as such.
+//! `auto` leaves it to a guard's destructor, which runs both when the `async
+//! fn` returns and when it is dropped at an `.await`. `manual` does it from
+//! `PinnedDrop`, with no completion bookkeeping at all: the `OnDrop` is
+//! dropped as soon as the `async fn` around it finishes.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use futures_lite::future::{block_on, pending};
+//! use async_intuition::{testing::poll_once, two_ways::on_drop::{auto, manual}};
+//!
+//! let calls = Cell::new(0);
+//! let count = || calls.set(calls.get() + 1);
+//!
+//! // completed.
+//! assert_eq!(block_on(auto::on_drop(async { 1 }, count)), 1);
+//! assert_eq!(calls.get(), 1);
+//! assert_eq!(block_on(manual::on_drop(async { 2 }, count)), 2);
+//! assert_eq!(calls.get(), 2);
+//!
+//! // dropped early.
+//! let mut fut = Box::pin(auto::on_drop(pending::<()>(), count));
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert_eq!(calls.get(), 2);
+//! drop(fut);
+//! assert_eq!(calls.get(), 3);
+//!
+//! let mut fut = Box::pin(manual::on_drop(pending::<()>(), count));
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert_eq!(calls.get(), 3);
+//! drop(fut);
+//! assert_eq!(calls.get(), 4);
+//!
+//! // never polled: neither the guard nor the `OnDrop` has been created yet.
+//! drop(auto::on_drop(pending::<()>(), count));
+//! drop(manual::on_drop(pending::<()>(), count));
+//! assert_eq!(calls.get(), 4);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn on_drop<Fut, F>(fut: Fut, f: F) -> Fut::Output
+    where
+        Fut: Future,
+        F: FnOnce(),
+    {
+        let _guard = Guard(Some(f));
+        fut.await
+    }
+
+    struct Guard<F: FnOnce()>(Option<F>);
+
+    impl<F: FnOnce()> Drop for Guard<F> {
+        fn drop(&mut self) {
+            if let Some(f) = self.0.take() {
+                f();
+            }
+        }
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::{pin_project, pinned_drop};
+
+    pub async fn on_drop<Fut, F>(fut: Fut, f: F) -> Fut::Output
+    where
+        Fut: Future,
+        F: FnOnce(),
+    {
+        OnDrop { fut, f: Some(f) }.await
+    }
+
+    #[pin_project(PinnedDrop)]
+    struct OnDrop<Fut, F: FnOnce()> {
+        #[pin]
+        fut: Fut,
+        // only `None` while being dropped.
+        f: Option<F>,
+    }
+
+    impl<Fut: Future, F: FnOnce()> Future for OnDrop<Fut, F> {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            self.project().fut.poll(cx)
+        }
+    }
+
+    #[pinned_drop]
+    impl<Fut, F: FnOnce()> PinnedDrop for OnDrop<Fut, F> {
+        fn drop(self: Pin<&mut Self>) {
+            if let Some(f) = self.project().f.take() {
+                f();
+            }
+        }
+    }
+}
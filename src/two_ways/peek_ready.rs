@@ -0,0 +1,86 @@
+//! Poll a future without being handed its output: polling only reports
+//! whether the future has finished, [`is_ready`](manual::PeekReady::is_ready)
+//! can be asked at any time afterwards, and the output stays put until
+//! [`take`](manual::PeekReady::take) moves it out.
+//!
+//! A scheduler juggling several of these can poll them all, look at which are
+//! ready, and only then decide whose output to consume, rather than having to
+//! deal with every output the moment its future completes. It is a
+//! [`MaybeDone`](super::maybe_done::manual::MaybeDone) with a
+//! readiness check on the outside. There is no `auto` version, for the same
+//! reason there isn't one of that.
+//!
+//! ```
+//! # use std::{future::Future, pin::pin, task::Poll};
+//! # use futures_lite::future::yield_now;
+//! use async_intuition::{testing::noop_context, two_ways::peek_ready::manual::peek_ready};
+//!
+//! let mut fut = pin!(peek_ready(async {
+//!     yield_now().await;
+//!     "done"
+//! }));
+//! let mut cx = noop_context();
+//! assert!(!fut.is_ready());
+//! assert_eq!(fut.as_mut().take(), None);
+//!
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+//! assert!(!fut.is_ready());
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(()));
+//! // still there, however many times anyone looks.
+//! assert!(fut.is_ready());
+//! assert!(fut.is_ready());
+//!
+//! assert_eq!(fut.as_mut().take(), Some("done"));
+//! assert!(!fut.is_ready());
+//! assert_eq!(fut.as_mut().take(), None);
+//! ```
+
+pub mod manual {
+    use core::{fmt, future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    use crate::two_ways::maybe_done::manual::{maybe_done, MaybeDone};
+
+    pub fn peek_ready<Fut: Future>(fut: Fut) -> PeekReady<Fut> {
+        PeekReady {
+            inner: maybe_done(fut),
+        }
+    }
+
+    #[pin_project]
+    pub struct PeekReady<Fut: Future> {
+        #[pin]
+        inner: MaybeDone<Fut>,
+    }
+
+    impl<Fut: Future> fmt::Debug for PeekReady<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("PeekReady")
+                .field("ready", &self.is_ready())
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<Fut: Future> PeekReady<Fut> {
+        /// Whether the future has completed and its output is waiting to be
+        /// taken.
+        pub fn is_ready(&self) -> bool {
+            matches!(self.inner, MaybeDone::Done { .. })
+        }
+
+        /// Move the output out, if there is one. Afterwards, `is_ready` is
+        /// `false` again.
+        pub fn take(self: Pin<&mut Self>) -> Option<Fut::Output> {
+            self.project().inner.take_output()
+        }
+    }
+
+    impl<Fut: Future> Future for PeekReady<Fut> {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            self.project().inner.poll(cx)
+        }
+    }
+}
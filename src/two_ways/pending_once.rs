@@ -0,0 +1,69 @@
+//! Return `Pending` on the first poll, whatever the inner future would have
+//! done, then hand every later poll on to the inner future.
+//!
+//! This is [`yield_now`](fn@crate::basics::yield_now) followed by the inner
+//! future, and it wakes itself for the same reason. It makes a handy test
+//! fixture: wrapped in this, even an immediately ready future forces its
+//! executor to reschedule the task at least once.
+//!
+//! ```
+//! # use std::sync::{atomic::{AtomicUsize, Ordering}, Arc};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{
+//!     basics::ready,
+//!     two_ways::{count_polls::manual::count_polls, pending_once::{auto, manual}},
+//! };
+//!
+//! let polls = Arc::new(AtomicUsize::new(0));
+//! let out = block_on(count_polls(auto::pending_once(ready(1)), Arc::clone(&polls)));
+//! assert_eq!((out, polls.swap(0, Ordering::Relaxed)), (1, 2));
+//! let out = block_on(count_polls(manual::pending_once(ready(2)), Arc::clone(&polls)));
+//! assert_eq!((out, polls.swap(0, Ordering::Relaxed)), (2, 2));
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    use crate::basics::yield_now;
+
+    pub async fn pending_once<Fut: Future>(fut: Fut) -> Fut::Output {
+        yield_now().await;
+        fut.await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn pending_once<Fut: Future>(fut: Fut) -> Fut::Output {
+        PendingOnce {
+            yielded: false,
+            fut,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct PendingOnce<Fut> {
+        yielded: bool,
+        #[pin]
+        fut: Fut,
+    }
+
+    impl<Fut: Future> Future for PendingOnce<Fut> {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if !*this.yielded {
+                *this.yielded = true;
+                // nothing else will wake the task for a `Pending` it made up.
+                cx.waker().wake_by_ref();
+                return Poll::Pending;
+            }
+            this.fut.poll(cx)
+        }
+    }
+}
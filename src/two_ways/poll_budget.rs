@@ -0,0 +1,111 @@
+//! Run a future, allowing it at most `budget` polls. A future which is still
+//! pending after the last of those is given up on straight away: that same
+//! poll resolves to [`BudgetExceeded`], rather than waiting for a wakeup which
+//! may never come.
+//!
+//! A well-behaved future is only polled when there's a chance it can make
+//! progress, so the number of polls it takes is usually small and
+//! predictable. One which goes on and on, waking itself without ever
+//! finishing, is stuck in a loop with its executor, and a budget turns that
+//! into an error to go and look at, rather than a hang.
+//!
+//! There is no `auto` version, for the same reason as with
+//! [`count_polls`](super::count_polls): `.await` doesn't let the caller see
+//! individual polls, so it can't count them either.
+//!
+//! ```
+//! # use std::{future::pending, pin::pin, sync::{atomic::{AtomicUsize, Ordering}, Arc}, task::Poll};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{
+//!     testing::{poll_counter, poll_once},
+//!     two_ways::{
+//!         count_polls::manual::count_polls,
+//!         poll_budget::{manual::poll_budget, BudgetExceeded},
+//!     },
+//! };
+//!
+//! // two `Pending`s and then done: three polls, which just fits.
+//! assert_eq!(block_on(poll_budget(poll_counter(2, "done"), 3)), Ok("done"));
+//!
+//! // wakes itself forever, and gets cut off after exactly three polls.
+//! let polls = Arc::new(AtomicUsize::new(0));
+//! let never = count_polls(poll_counter(usize::MAX, ()), Arc::clone(&polls));
+//! assert_eq!(block_on(poll_budget(never, 3)), Err(BudgetExceeded));
+//! assert_eq!(polls.load(Ordering::Relaxed), 3);
+//!
+//! // never wakes at all, so the third poll has to be the one to give up.
+//! let polls = Arc::new(AtomicUsize::new(0));
+//! let stuck = count_polls(pending::<()>(), Arc::clone(&polls));
+//! let mut fut = pin!(poll_budget(stuck, 3));
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert_eq!(poll_once(fut.as_mut()), Poll::Ready(Err(BudgetExceeded)));
+//! assert_eq!(polls.load(Ordering::Relaxed), 3);
+//! ```
+
+use core::fmt;
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct BudgetExceeded;
+
+impl fmt::Display for BudgetExceeded {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("poll budget exceeded")
+    }
+}
+
+impl core::error::Error for BudgetExceeded {}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    use super::BudgetExceeded;
+
+    pub async fn poll_budget<Fut: Future>(
+        fut: Fut,
+        budget: u32,
+    ) -> Result<Fut::Output, BudgetExceeded> {
+        PollBudget {
+            remaining: budget,
+            fut,
+            done: false,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct PollBudget<Fut> {
+        // polls of `fut` still allowed.
+        remaining: u32,
+        #[pin]
+        fut: Fut,
+        done: bool,
+    }
+
+    impl<Fut: Future> Future for PollBudget<Fut> {
+        type Output = Result<Fut::Output, BudgetExceeded>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+
+            // with no budget at all, `fut` is never polled.
+            if *this.remaining > 0 {
+                *this.remaining -= 1;
+                if let Poll::Ready(out) = this.fut.poll(cx) {
+                    *this.done = true;
+                    return Poll::Ready(Ok(out));
+                }
+                if *this.remaining > 0 {
+                    return Poll::Pending;
+                }
+            }
+            *this.done = true;
+            Poll::Ready(Err(BudgetExceeded))
+        }
+    }
+}
@@ -0,0 +1,100 @@
+//! A future whose `poll` is whatever closure you hand it, for writing one-off
+//! futures without declaring a type.
+//!
+//! There is no `auto` version: `async` blocks never get to see the
+//! [`Context`](std::task::Context), which is the whole point here. Instead the
+//! closure is shown stored two ways: as a named field behind an `async fn`,
+//! the way the other `manual` modules work, and as a public newtype which can
+//! be named, stored, and polled directly.
+//!
+//! ```
+//! # use std::task::Poll;
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::poll_fn::{manual, newtype};
+//!
+//! let countdown = |mut remaining: u32| {
+//!     move |cx: &mut core::task::Context<'_>| {
+//!         if remaining == 0 {
+//!             return Poll::Ready("liftoff");
+//!         }
+//!         remaining -= 1;
+//!         cx.waker().wake_by_ref();
+//!         Poll::Pending
+//!     }
+//! };
+//!
+//! assert_eq!(block_on(manual::poll_fn(countdown(3))), "liftoff");
+//! assert_eq!(block_on(newtype::poll_fn(countdown(3))), "liftoff");
+//! ```
+
+pub mod manual {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    pub async fn poll_fn<T, F>(f: F) -> T
+    where
+        F: FnMut(&mut Context<'_>) -> Poll<T>,
+    {
+        PollFn { f }.await
+    }
+
+    struct PollFn<F> {
+        f: F,
+    }
+
+    // the closure is only ever called through `&mut`, never pinned, so moving
+    // it around is fine even if it captures something `!Unpin`.
+    impl<F> Unpin for PollFn<F> {}
+
+    impl<T, F> Future for PollFn<F>
+    where
+        F: FnMut(&mut Context<'_>) -> Poll<T>,
+    {
+        type Output = T;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            (self.f)(cx)
+        }
+    }
+}
+
+pub mod newtype {
+    use core::{
+        fmt,
+        future::Future,
+        pin::Pin,
+        task::{Context, Poll},
+    };
+
+    pub fn poll_fn<T, F>(f: F) -> PollFn<F>
+    where
+        F: FnMut(&mut Context<'_>) -> Poll<T>,
+    {
+        PollFn(f)
+    }
+
+    pub struct PollFn<F>(F);
+
+    impl<F> fmt::Debug for PollFn<F> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("PollFn").finish_non_exhaustive()
+        }
+    }
+
+    // see `manual::PollFn`.
+    impl<F> Unpin for PollFn<F> {}
+
+    impl<T, F> Future for PollFn<F>
+    where
+        F: FnMut(&mut Context<'_>) -> Poll<T>,
+    {
+        type Output = T;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut Context<'_>) -> Poll<Self::Output> {
+            (self.0)(cx)
+        }
+    }
+}
@@ -0,0 +1,62 @@
+//! Poll a future exactly once, resolving to its output if it was ready and
+//! `None` if it wasn't, rather than waiting around. Either way, the inner
+//! future is dropped: an unfinished one is abandoned, much like the loser of
+//! a [`select`](super::select).
+//!
+//! ```
+//! # use futures_lite::future::{block_on, pending, yield_now};
+//! use async_intuition::two_ways::poll_immediate::{auto, manual};
+//!
+//! assert_eq!(block_on(auto::poll_immediate(async { 1 })), Some(1));
+//! assert_eq!(block_on(manual::poll_immediate(async { 1 })), Some(1));
+//!
+//! assert_eq!(block_on(auto::poll_immediate(pending::<u8>())), None);
+//! assert_eq!(block_on(manual::poll_immediate(pending::<u8>())), None);
+//!
+//! // would be ready on the next poll, but there isn't one.
+//! assert_eq!(block_on(manual::poll_immediate(yield_now())), None);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn poll_immediate<Fut: Future>(fut: Fut) -> Option<Fut::Output> {
+        futures_lite::future::poll_once(fut).await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn poll_immediate<Fut: Future>(fut: Fut) -> Option<Fut::Output> {
+        PollImmediate { fut: Some(fut) }.await
+    }
+
+    #[pin_project]
+    struct PollImmediate<Fut> {
+        // `None` once polled, which doubles as the "completed" state.
+        #[pin]
+        fut: Option<Fut>,
+    }
+
+    impl<Fut: Future> Future for PollImmediate<Fut> {
+        type Output = Option<Fut::Output>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            let fut = this
+                .fut
+                .as_mut()
+                .as_pin_mut()
+                .expect("`async fn` resumed after completion");
+            let output = match fut.poll(cx) {
+                Poll::Ready(out) => Some(out),
+                Poll::Pending => None,
+            };
+            this.fut.set(None);
+            Poll::Ready(output)
+        }
+    }
+}
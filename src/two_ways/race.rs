@@ -0,0 +1,98 @@
+//! Run two futures with the same output type concurrently, resolving to the
+//! output of whichever finishes first.
+//!
+//! If `a` were always polled before `b`, `a` would win every time both happen
+//! to be ready on the same poll. Worse, if polling `a` keeps waking the task
+//! (say it is a stream of work which is never quite done), `b` might only be
+//! reached after `a` completes, or not at all. To stay fair, the manual version
+//! alternates which side goes first on each poll.
+//!
+//! Given two futures which become ready on the same poll, either one can win
+//! depending on how many polls that takes:
+//!
+//! ```
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::race::manual;
+//!
+//! let yielding = |yields, name| async move {
+//!     for _ in 0..yields {
+//!         yield_now().await;
+//!     }
+//!     name
+//! };
+//!
+//! let winners: Vec<_> = (0..4)
+//!     .map(|yields| block_on(manual::race(yielding(yields, 'a'), yielding(yields, 'b'))))
+//!     .collect();
+//! assert!(winners.contains(&'a'));
+//! assert!(winners.contains(&'b'));
+//! ```
+
+// `futures_lite::future::race` picks who goes first at random, which needs a
+// source of randomness from std.
+#[cfg(feature = "std")]
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn race<T, A, B>(a: A, b: B) -> T
+    where
+        A: Future<Output = T>,
+        B: Future<Output = T>,
+    {
+        futures_lite::future::race(a, b).await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn race<T, A, B>(a: A, b: B) -> T
+    where
+        A: Future<Output = T>,
+        B: Future<Output = T>,
+    {
+        Race {
+            b_first: false,
+            a,
+            b,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Race<A, B> {
+        b_first: bool,
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+    }
+
+    impl<T, A, B> Future for Race<A, B>
+    where
+        A: Future<Output = T>,
+        B: Future<Output = T>,
+    {
+        type Output = T;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            let b_first = *this.b_first;
+            *this.b_first = !b_first;
+
+            if b_first {
+                if let Poll::Ready(out) = this.b.poll(cx) {
+                    return Poll::Ready(out);
+                }
+                this.a.poll(cx)
+            } else {
+                if let Poll::Ready(out) = this.a.poll(cx) {
+                    return Poll::Ready(out);
+                }
+                this.b.poll(cx)
+            }
+        }
+    }
+}
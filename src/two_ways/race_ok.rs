@@ -0,0 +1,116 @@
+//! Run any number of fallible futures of the same type concurrently,
+//! resolving to the first success. This is [`first_ok`](super::first_ok) for
+//! a `Vec`, except that when every future fails, all of their errors are
+//! returned, in the order the failures happened.
+//!
+//! There is no `auto` version: as with [`select_all`](super::select_all),
+//! `async` has no way to wait on whichever of a dynamic number of futures is
+//! first.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{testing::poll_counter, two_ways::race_ok::manual::race_ok};
+//!
+//! type R = Result<u32, &'static str>;
+//!
+//! // the first success wins, even after other futures have failed.
+//! let futs = vec![
+//!     poll_counter(3, R::Ok(3)),
+//!     poll_counter(0, R::Err("zero")),
+//!     poll_counter(2, R::Ok(2)),
+//! ];
+//! assert_eq!(block_on(race_ok(futs)), Ok(2));
+//!
+//! // when everything fails, the errors come back in the order they happened.
+//! let futs = vec![
+//!     poll_counter(2, R::Err("two")),
+//!     poll_counter(0, R::Err("zero")),
+//!     poll_counter(1, R::Err("one")),
+//! ];
+//! assert_eq!(block_on(race_ok(futs)), Err(vec!["zero", "one", "two"]));
+//!
+//! // with nothing to race, there are no errors to wait for either.
+//! let nothing: Vec<std::future::Pending<Result<(), ()>>> = Vec::new();
+//! assert_eq!(block_on(race_ok(nothing)), Err(vec![]));
+//! ```
+
+pub mod manual {
+    use alloc::{boxed::Box, vec::Vec};
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    pub async fn race_ok<T, E, Fut>(futs: Vec<Fut>) -> Result<T, Vec<E>>
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        RaceOk {
+            elems: futs.into_iter().map(Some).collect::<Box<_>>().into(),
+            errors: Vec::new(),
+            done: false,
+        }
+        .await
+    }
+
+    // pinned in a boxed slice for the same reason as in `join_all`. A future
+    // which fails is dropped in place by setting its slot to `None`, and never
+    // polled again.
+    struct RaceOk<Fut, E> {
+        elems: Pin<Box<[Option<Fut>]>>,
+        errors: Vec<E>,
+        done: bool,
+    }
+
+    // the futures are pinned by the box rather than by `RaceOk`, and the
+    // errors are only ever moved, never pinned.
+    impl<Fut, E> Unpin for RaceOk<Fut, E> {}
+
+    fn elems<Fut>(
+        elems: &mut Pin<Box<[Option<Fut>]>>,
+    ) -> impl Iterator<Item = Pin<&mut Option<Fut>>> {
+        // SAFETY: each element is pinned in place along with the slice, and
+        // none of them is ever moved out of it.
+        let elems = unsafe { elems.as_mut().get_unchecked_mut() };
+        elems
+            .iter_mut()
+            .map(|elem| unsafe { Pin::new_unchecked(elem) })
+    }
+
+    impl<T, E, Fut> Future for RaceOk<Fut, E>
+    where
+        Fut: Future<Output = Result<T, E>>,
+    {
+        type Output = Result<T, Vec<E>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            if this.done {
+                panic!("`async fn` resumed after completion");
+            }
+
+            let mut any_live = false;
+            for mut elem in elems(&mut this.elems) {
+                let Some(fut) = elem.as_mut().as_pin_mut() else {
+                    continue;
+                };
+                match fut.poll(cx) {
+                    Poll::Ready(Ok(t)) => {
+                        this.done = true;
+                        return Poll::Ready(Ok(t));
+                    }
+                    Poll::Ready(Err(e)) => {
+                        this.errors.push(e);
+                        elem.set(None);
+                    }
+                    Poll::Pending => any_live = true,
+                }
+            }
+
+            if any_live {
+                Poll::Pending
+            } else {
+                // also reached straight away when there were no futures.
+                this.done = true;
+                Poll::Ready(Err(core::mem::take(&mut this.errors)))
+            }
+        }
+    }
+}
@@ -0,0 +1,131 @@
+//! Run futures produced by a closure one after another, `n` times in total.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::repeat::{auto, manual};
+//!
+//! let runs = Cell::new(0);
+//! let bump = || async {
+//!     yield_now().await;
+//!     runs.set(runs.get() + 1);
+//! };
+//!
+//! block_on(auto::repeat(3, bump));
+//! assert_eq!(runs.get(), 3);
+//! block_on(manual::repeat(3, bump));
+//! assert_eq!(runs.get(), 6);
+//! block_on(manual::repeat(0, bump));
+//! assert_eq!(runs.get(), 6);
+//! ```
+//!
+//! The `_mut` siblings take an `FnMut`, for a closure which mutates what it
+//! captures rather than going through a `Cell`:
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::repeat::{auto, manual};
+//!
+//! let mut started = Vec::new();
+//! let mut start = || {
+//!     started.push(started.len());
+//!     async {}
+//! };
+//! block_on(auto::repeat_mut(2, &mut start));
+//! block_on(manual::repeat_mut(3, &mut start));
+//! assert_eq!(started, [0, 1, 2, 3, 4]);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn repeat<F, Fut>(n: u32, f: F)
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        for _ in 0..n {
+            f().await;
+        }
+    }
+
+    pub async fn repeat_mut<F, Fut>(n: u32, mut f: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        for _ in 0..n {
+            f().await;
+        }
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn repeat<F, Fut>(n: u32, f: F)
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        Repeat {
+            remaining: n,
+            f,
+            current: None,
+        }
+        .await
+    }
+
+    pub async fn repeat_mut<F, Fut>(n: u32, f: F)
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        Repeat {
+            remaining: n,
+            f,
+            current: None,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Repeat<F, Fut> {
+        // runs left to start, not counting `current`.
+        remaining: u32,
+        f: F,
+        #[pin]
+        current: Option<Fut>,
+    }
+
+    // `FnMut` works here for the same reason as in `until_equals::manual`.
+    impl<F, Fut> Future for Repeat<F, Fut>
+    where
+        F: FnMut() -> Fut,
+        Fut: Future<Output = ()>,
+    {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            loop {
+                let current = if let Some(fut) = this.current.as_mut().as_pin_mut() {
+                    fut
+                } else if *this.remaining == 0 {
+                    return Poll::Ready(());
+                } else {
+                    *this.remaining -= 1;
+                    this.current.set(Some((this.f)()));
+                    unsafe { this.current.as_mut().as_pin_mut().unwrap_unchecked() }
+                };
+
+                match current.poll(cx) {
+                    Poll::Ready(()) => this.current.set(None),
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+    }
+}
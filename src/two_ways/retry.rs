@@ -0,0 +1,109 @@
+//! Keep running futures produced by `f` until one resolves to `Ok`, giving up
+//! with the last `Err` once `max_attempts` have failed. At least one attempt
+//! is always made, even if `max_attempts` is zero.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::retry::{auto, manual};
+//!
+//! let attempt = Cell::new(0);
+//! let third_time_lucky = || async {
+//!     attempt.set(attempt.get() + 1);
+//!     if attempt.get() % 3 == 0 {
+//!         Ok(attempt.get())
+//!     } else {
+//!         Err(attempt.get())
+//!     }
+//! };
+//!
+//! assert_eq!(block_on(auto::retry(5, third_time_lucky)), Ok(3));
+//! assert_eq!(block_on(manual::retry(5, third_time_lucky)), Ok(6));
+//!
+//! // the final error is returned once the attempts run out.
+//! attempt.set(0);
+//! assert_eq!(block_on(auto::retry(2, third_time_lucky)), Err(2));
+//! attempt.set(0);
+//! assert_eq!(block_on(manual::retry(2, third_time_lucky)), Err(2));
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn retry<F, Fut, T, E>(max_attempts: u32, f: F) -> Result<T, E>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        let mut attempts_left = max_attempts;
+        loop {
+            attempts_left = attempts_left.saturating_sub(1);
+            match f().await {
+                Ok(t) => return Ok(t),
+                Err(e) if attempts_left == 0 => return Err(e),
+                Err(_) => {}
+            }
+        }
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn retry<F, Fut, T, E>(max_attempts: u32, f: F) -> Result<T, E>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        Retry {
+            attempts_left: max_attempts,
+            f,
+            attempt: None,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Retry<F, Fut> {
+        // attempts left to start, not counting `attempt`.
+        attempts_left: u32,
+        f: F,
+        #[pin]
+        attempt: Option<Fut>,
+    }
+
+    impl<F, Fut, T, E> Future for Retry<F, Fut>
+    where
+        F: Fn() -> Fut,
+        Fut: Future<Output = Result<T, E>>,
+    {
+        type Output = Result<T, E>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            loop {
+                let attempt = if let Some(fut) = this.attempt.as_mut().as_pin_mut() {
+                    fut
+                } else {
+                    *this.attempts_left = this.attempts_left.saturating_sub(1);
+                    this.attempt.set(Some((this.f)()));
+                    unsafe { this.attempt.as_mut().as_pin_mut().unwrap_unchecked() }
+                };
+
+                match attempt.poll(cx) {
+                    Poll::Ready(result) => {
+                        this.attempt.set(None);
+                        // an exhausted retry resolves rather than spinning on
+                        // attempts it isn't allowed to make.
+                        if result.is_ok() || *this.attempts_left == 0 {
+                            return Poll::Ready(result);
+                        }
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+    }
+}
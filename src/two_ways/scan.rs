@@ -0,0 +1,131 @@
+//! Fold the outputs of repeatedly generated futures into an accumulator. Each
+//! item comes from awaiting a fresh future from `next`, and the closure gets
+//! the accumulator along with the item, deciding whether to
+//! [`Continue`](ControlFlow::Continue) with a new accumulator or
+//! [`Break`](ControlFlow::Break) with the final one.
+//!
+//! This is [`unfold`](super::unfold) with the future-producing half split out,
+//! and [`until_equals`](super::until_equals) with something to show for it.
+//!
+//! ```
+//! # use std::{cell::Cell, ops::ControlFlow};
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::scan::{auto, manual};
+//!
+//! let counter = Cell::new(0);
+//! let next = || async {
+//!     yield_now().await;
+//!     counter.set(counter.get() + 1);
+//!     counter.get()
+//! };
+//!
+//! // multiply 1, 2, 3, ... together until the product passes 100.
+//! let product = |acc: u64, n: u64| {
+//!     let acc = acc * n;
+//!     if acc > 100 {
+//!         ControlFlow::Break(acc)
+//!     } else {
+//!         ControlFlow::Continue(acc)
+//!     }
+//! };
+//!
+//! assert_eq!(block_on(auto::scan(1, next, product)), 120);
+//! assert_eq!(counter.get(), 5);
+//! counter.set(0);
+//! assert_eq!(block_on(manual::scan(1, next, product)), 120);
+//! assert_eq!(counter.get(), 5);
+//! ```
+
+#[cfg(doc)]
+use core::ops::ControlFlow;
+
+pub mod auto {
+    use core::{future::Future, ops::ControlFlow};
+
+    pub async fn scan<S, G, Fut, F>(init: S, mut next: G, mut f: F) -> S
+    where
+        G: FnMut() -> Fut,
+        Fut: Future,
+        F: FnMut(S, Fut::Output) -> ControlFlow<S, S>,
+    {
+        let mut acc = init;
+        loop {
+            match f(acc, next().await) {
+                ControlFlow::Continue(more) => acc = more,
+                ControlFlow::Break(done) => return done,
+            }
+        }
+    }
+}
+
+pub mod manual {
+    use core::{
+        future::Future,
+        ops::ControlFlow,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn scan<S, G, Fut, F>(init: S, next: G, f: F) -> S
+    where
+        G: FnMut() -> Fut,
+        Fut: Future,
+        F: FnMut(S, Fut::Output) -> ControlFlow<S, S>,
+    {
+        Scan {
+            acc: Some(init),
+            next,
+            f,
+            fut: None,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Scan<S, G, F, Fut> {
+        // `None` once handed back as the output.
+        acc: Option<S>,
+        next: G,
+        f: F,
+        // the future producing the next item, recreated for every item.
+        #[pin]
+        fut: Option<Fut>,
+    }
+
+    impl<S, G, Fut, F> Future for Scan<S, G, F, Fut>
+    where
+        G: FnMut() -> Fut,
+        Fut: Future,
+        F: FnMut(S, Fut::Output) -> ControlFlow<S, S>,
+    {
+        type Output = S;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            // as in `until_equals`, a `Continue` has to go straight on to
+            // the next item: nothing would wake us if we returned `Pending`.
+            loop {
+                if this.acc.is_none() {
+                    panic!("`async fn` resumed after completion");
+                }
+                let fut = match this.fut.as_mut().as_pin_mut() {
+                    Some(fut) => fut,
+                    None => {
+                        this.fut.set(Some((this.next)()));
+                        this.fut.as_mut().as_pin_mut().expect("just set")
+                    }
+                };
+
+                let item = ready!(fut.poll(cx));
+                this.fut.set(None);
+                let acc = this.acc.take().expect("checked above");
+                match (this.f)(acc, item) {
+                    ControlFlow::Continue(more) => *this.acc = Some(more),
+                    ControlFlow::Break(done) => return Poll::Ready(done),
+                }
+            }
+        }
+    }
+}
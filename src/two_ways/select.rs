@@ -0,0 +1,171 @@
+//! Run two futures concurrently, resolving to the output of whichever finishes
+//! first. The other future is dropped without ever completing.
+//!
+//! ```
+//! # use futures_lite::future::{block_on, pending, yield_now};
+//! use async_intuition::two_ways::select::{auto, manual, Either};
+//!
+//! let fast = || async {
+//!     yield_now().await;
+//!     "fast"
+//! };
+//!
+//! let winner = block_on(auto::select(fast(), pending::<u32>()));
+//! assert_eq!(winner, Either::Left("fast"));
+//! let winner = block_on(manual::select(fast(), pending::<u32>()));
+//! assert_eq!(winner, Either::Left("fast"));
+//! ```
+//!
+//! The loser is dropped as soon as there is a winner, and neither future is
+//! dropped a second time when the select itself goes. Since this leans on
+//! `ManuallyDrop`, it is worth running under `cargo +nightly miri test`:
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! # use futures_lite::future::yield_now;
+//! use async_intuition::{testing::poll_once, two_ways::select::manual};
+//!
+//! thread_local! {
+//!     static DROPPED: RefCell<Vec<&'static str>> = RefCell::default();
+//! }
+//!
+//! struct Payload(&'static str);
+//!
+//! impl Drop for Payload {
+//!     fn drop(&mut self) {
+//!         DROPPED.with_borrow_mut(|dropped| dropped.push(self.0));
+//!     }
+//! }
+//!
+//! let take_dropped = || DROPPED.take();
+//! // holds on to a payload until it resolves on the given poll.
+//! let holding = |poll: usize, name| {
+//!     let payload = Payload(name);
+//!     async move {
+//!         let _payload = payload;
+//!         for _ in 1..poll {
+//!             yield_now().await;
+//!         }
+//!     }
+//! };
+//!
+//! // racing: dropping the select drops both futures.
+//! let mut select = Box::pin(manual::select(holding(2, "a"), holding(2, "b")));
+//! assert!(poll_once(select.as_mut()).is_pending());
+//! drop(select);
+//! assert_eq!(take_dropped(), ["a", "b"]);
+//!
+//! // done: the winner finished and the loser was dropped on the spot.
+//! let mut select = Box::pin(manual::select(holding(1, "a"), holding(2, "b")));
+//! assert!(poll_once(select.as_mut()).is_ready());
+//! assert_eq!(take_dropped(), ["a", "b"]);
+//! drop(select);
+//! assert!(take_dropped().is_empty());
+//! ```
+
+pub use crate::either::Either;
+
+pub mod auto {
+    use core::future::Future;
+
+    use crate::either::Either;
+
+    pub async fn select<A: Future, B: Future>(a: A, b: B) -> Either<A::Output, B::Output> {
+        futures_lite::future::or(async { Either::Left(a.await) }, async {
+            Either::Right(b.await)
+        })
+        .await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, mem::ManuallyDrop, pin::Pin, task::Poll};
+
+    use pin_project::{pin_project, pinned_drop};
+
+    use crate::either::Either;
+
+    pub async fn select<A: Future, B: Future>(a: A, b: B) -> Either<A::Output, B::Output> {
+        Select {
+            state: State::default(),
+            a: ManuallyDrop::new(a),
+            b: ManuallyDrop::new(b),
+        }
+        .await
+    }
+
+    #[derive(Clone, Copy, Default)]
+    enum State {
+        #[default]
+        Racing,
+        Done,
+    }
+
+    // both futures are wrapped in `ManuallyDrop` so that the loser can be
+    // dropped the moment a winner is known, rather than whenever the `Select`
+    // itself happens to be dropped.
+    #[pin_project(PinnedDrop)]
+    struct Select<A, B> {
+        state: State,
+        #[pin]
+        a: ManuallyDrop<A>,
+        #[pin]
+        b: ManuallyDrop<B>,
+    }
+
+    impl<A, B> Select<A, B> {
+        /// # Safety
+        ///
+        /// Must be called at most once, and `a` and `b` must never be accessed
+        /// again afterwards.
+        unsafe fn drop_futures(self: Pin<&mut Self>) {
+            let this = self.project();
+            // dropping in place upholds the pinning guarantee: neither future
+            // is moved before its destructor runs.
+            unsafe {
+                ManuallyDrop::drop(this.a.get_unchecked_mut());
+                ManuallyDrop::drop(this.b.get_unchecked_mut());
+            }
+        }
+    }
+
+    impl<A: Future, B: Future> Future for Select<A, B> {
+        type Output = Either<A::Output, B::Output>;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.as_mut().project();
+            let State::Racing = this.state else {
+                panic!("`async fn` resumed after completion");
+            };
+
+            // SAFETY: `State::Racing` means neither future has been dropped.
+            let a = unsafe { this.a.map_unchecked_mut(|a| &mut **a) };
+            let output = if let Poll::Ready(out) = a.poll(cx) {
+                Either::Left(out)
+            } else {
+                // SAFETY: as above.
+                let b = unsafe { this.b.map_unchecked_mut(|b| &mut **b) };
+                match b.poll(cx) {
+                    Poll::Ready(out) => Either::Right(out),
+                    Poll::Pending => return Poll::Pending,
+                }
+            };
+
+            *this.state = State::Done;
+            // SAFETY: the state is now `Done`, so neither future will be
+            // touched again, including by `PinnedDrop`.
+            unsafe { self.drop_futures() };
+            Poll::Ready(output)
+        }
+    }
+
+    #[pinned_drop]
+    impl<A, B> PinnedDrop for Select<A, B> {
+        fn drop(mut self: Pin<&mut Self>) {
+            if let State::Racing = self.as_mut().project().state {
+                // SAFETY: in `State::Racing`, neither future has been dropped.
+                unsafe { self.drop_futures() };
+            }
+        }
+    }
+}
@@ -0,0 +1,75 @@
+//! Run any number of futures of the same type concurrently, resolving as soon
+//! as one finishes to its index, its output, and the rest of the futures, so
+//! the caller can keep waiting on them.
+//!
+//! Handing the unfinished futures back means moving them out from wherever
+//! they were being polled. A pinned future promises never to move again, so
+//! the futures must either not care about being moved (`Fut: Unpin`, as here)
+//! or live behind a pointer which can move on their behalf (`Pin<Box<_>>`,
+//! which is always `Unpin`, so boxing works with this version too).
+//!
+//! With no futures at all there is nothing to finish first, so an empty `Vec`
+//! stays pending forever.
+//!
+//! There is no `auto` version: `async` code has no way to wait on "whichever
+//! of these is first" without a combinator like this one.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{testing::poll_counter, two_ways::select_all::manual};
+//!
+//! let futs: Vec<_> = [4, 3, 2, 5]
+//!     .map(|n| Box::pin(async move { poll_counter(n, n).await }))
+//!     .into();
+//!
+//! let (index, output, rest) = block_on(manual::select_all(futs));
+//! assert_eq!((index, output), (2, 2));
+//! assert_eq!(rest.len(), 3);
+//! ```
+
+pub mod manual {
+    use alloc::vec::Vec;
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    pub async fn select_all<Fut: Future + Unpin>(futs: Vec<Fut>) -> (usize, Fut::Output, Vec<Fut>) {
+        SelectAll {
+            futs: Some(futs),
+            start: 0,
+        }
+        .await
+    }
+
+    struct SelectAll<Fut> {
+        // `None` once the remaining futures have been handed back.
+        futs: Option<Vec<Fut>>,
+        // where the next poll starts from. Rotating it means a future near
+        // the front which is always ready can't starve the ones behind it.
+        start: usize,
+    }
+
+    impl<Fut: Future + Unpin> Future for SelectAll<Fut> {
+        type Output = (usize, Fut::Output, Vec<Fut>);
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            let futs = this
+                .futs
+                .as_mut()
+                .expect("`async fn` resumed after completion");
+            let len = futs.len();
+            let start = this.start;
+            this.start = if len == 0 { 0 } else { (start + 1) % len };
+
+            for index in (start..len).chain(0..start) {
+                // `Unpin` is what makes this `Pin::new` (and the `remove`
+                // below) allowed.
+                if let Poll::Ready(output) = Pin::new(&mut futs[index]).poll(cx) {
+                    let mut rest = this.futs.take().unwrap();
+                    rest.remove(index);
+                    return Poll::Ready((index, output, rest));
+                }
+            }
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,93 @@
+//! Run two futures concurrently, resolving to the output of whichever
+//! finishes first, with ties always going to `a`.
+//!
+//! This is the other side of the tradeoff [`race`](super::race) makes.
+//! Polling `a` first on every poll is deterministic: given the same futures,
+//! the same side wins every time, which makes for reproducible tests and lets
+//! the caller give one side priority (a shutdown signal, say, over incoming
+//! work). The price is fairness: if `a` is ready on every poll, `b` never gets
+//! a look in.
+//!
+//! [`select`](super::select) happens to poll `a` first too, but is about
+//! dropping the loser early. Here the bias is the whole point.
+//!
+//! ```
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::{either::Either, two_ways::select_biased::{auto, manual}};
+//!
+//! let yielding = |yields, name| async move {
+//!     for _ in 0..yields {
+//!         yield_now().await;
+//!     }
+//!     name
+//! };
+//!
+//! // where `race` lets either side win a tie, here `a` wins every one.
+//! for yields in 0..4 {
+//!     let (a, b) = (yielding(yields, 'a'), yielding(yields, 'b'));
+//!     assert_eq!(block_on(auto::select_biased(a, b)), Either::Left('a'));
+//!     let (a, b) = (yielding(yields, 'a'), yielding(yields, 'b'));
+//!     assert_eq!(block_on(manual::select_biased(a, b)), Either::Left('a'));
+//! }
+//!
+//! // `b` still wins when it is strictly first.
+//! let (a, b) = (yielding(2, 'a'), yielding(1, 'b'));
+//! assert_eq!(block_on(manual::select_biased(a, b)), Either::Right('b'));
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    use crate::either::Either;
+
+    pub async fn select_biased<A: Future, B: Future>(a: A, b: B) -> Either<A::Output, B::Output> {
+        // `or` always polls its first future first.
+        futures_lite::future::or(async { Either::Left(a.await) }, async {
+            Either::Right(b.await)
+        })
+        .await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    use crate::either::Either;
+
+    pub async fn select_biased<A: Future, B: Future>(a: A, b: B) -> Either<A::Output, B::Output> {
+        SelectBiased { done: false, a, b }.await
+    }
+
+    #[pin_project]
+    struct SelectBiased<A, B> {
+        done: bool,
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+    }
+
+    impl<A: Future, B: Future> Future for SelectBiased<A, B> {
+        type Output = Either<A::Output, B::Output>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+            // unlike `race`, there is no alternating: `a` goes first on every
+            // poll, so `b` only wins if `a` isn't ready.
+            let output = if let Poll::Ready(out) = this.a.poll(cx) {
+                Either::Left(out)
+            } else if let Poll::Ready(out) = this.b.poll(cx) {
+                Either::Right(out)
+            } else {
+                return Poll::Pending;
+            };
+            *this.done = true;
+            Poll::Ready(output)
+        }
+    }
+}
@@ -0,0 +1,105 @@
+//! Run any number of fallible futures of the same type concurrently,
+//! resolving to the first success along with the futures which haven't
+//! finished yet. This is [`select_all`](super::select_all)'s "hand back the
+//! rest" combined with [`race_ok`](super::race_ok)'s patience with failures:
+//! a future which fails is dropped and its error kept, and only once every
+//! future has failed does this resolve to all of the errors, in the order
+//! the failures happened.
+//!
+//! As with `select_all`, handing back the unfinished futures means moving
+//! them, so they must be `Unpin` (or boxed, which makes them so). And there
+//! is no `auto` version, for the same reason.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{testing::poll_counter, two_ways::select_ok::manual::select_ok};
+//!
+//! type R = Result<u32, &'static str>;
+//!
+//! // the third future fails straight away, then the second succeeds.
+//! let futs = vec![
+//!     Box::pin(poll_counter(4, R::Ok(4))),
+//!     Box::pin(poll_counter(1, R::Ok(1))),
+//!     Box::pin(poll_counter(0, R::Err("zero"))),
+//!     Box::pin(poll_counter(2, R::Ok(2))),
+//! ];
+//! let (out, rest) = block_on(select_ok(futs)).unwrap();
+//! assert_eq!(out, 1);
+//! assert_eq!(rest.len(), 2);
+//!
+//! // the rest carry on from where they were.
+//! let (out, rest) = block_on(select_ok(rest)).unwrap();
+//! assert_eq!((out, rest.len()), (2, 1));
+//!
+//! let futs = vec![
+//!     Box::pin(poll_counter(1, R::Err("one"))),
+//!     Box::pin(poll_counter(0, R::Err("zero"))),
+//! ];
+//! assert_eq!(block_on(select_ok(futs)).err(), Some(vec!["zero", "one"]));
+//! ```
+
+pub mod manual {
+    use alloc::vec::Vec;
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    pub async fn select_ok<T, E, Fut>(futs: Vec<Fut>) -> Result<(T, Vec<Fut>), Vec<E>>
+    where
+        Fut: Future<Output = Result<T, E>> + Unpin,
+    {
+        SelectOk {
+            futs: Some(futs),
+            errors: Vec::new(),
+        }
+        .await
+    }
+
+    struct SelectOk<Fut, E> {
+        // `None` once resolved. A future which has failed is removed straight
+        // away, so that it is never polled again.
+        futs: Option<Vec<Fut>>,
+        errors: Vec<E>,
+    }
+
+    // the errors are only ever moved out, never pinned, and the futures are
+    // `Unpin` already.
+    impl<Fut: Unpin, E> Unpin for SelectOk<Fut, E> {}
+
+    impl<T, E, Fut> Future for SelectOk<Fut, E>
+    where
+        Fut: Future<Output = Result<T, E>> + Unpin,
+    {
+        type Output = Result<(T, Vec<Fut>), Vec<E>>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            let futs = this
+                .futs
+                .as_mut()
+                .expect("`async fn` resumed after completion");
+
+            let mut index = 0;
+            while index < futs.len() {
+                match Pin::new(&mut futs[index]).poll(cx) {
+                    Poll::Ready(Ok(output)) => {
+                        let mut rest = this.futs.take().unwrap();
+                        rest.remove(index);
+                        return Poll::Ready(Ok((output, rest)));
+                    }
+                    Poll::Ready(Err(err)) => {
+                        this.errors.push(err);
+                        // the next future slides into `index`.
+                        futs.remove(index);
+                    }
+                    Poll::Pending => index += 1,
+                }
+            }
+
+            if futs.is_empty() {
+                this.futs = None;
+                Poll::Ready(Err(core::mem::take(&mut this.errors)))
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+}
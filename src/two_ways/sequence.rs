@@ -0,0 +1,114 @@
+//! Run any number of futures of the same type one after another, resolving to
+//! all of their outputs. The [`a_then_b`](super::a_then_b) of a `Vec`: no
+//! future is polled at all until the one before it has completed.
+//!
+//! ```
+//! # use std::cell::RefCell;
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::sequence::{auto, manual};
+//!
+//! let log = RefCell::new(Vec::new());
+//! // the later futures take fewer polls, so run concurrently they would
+//! // finish in reverse.
+//! let steps = || {
+//!     (0..3)
+//!         .map(|i| {
+//!             let log = &log;
+//!             async move {
+//!                 log.borrow_mut().push(("start", i));
+//!                 for _ in i..3 {
+//!                     yield_now().await;
+//!                 }
+//!                 log.borrow_mut().push(("end", i));
+//!                 i * 10
+//!             }
+//!         })
+//!         .collect::<Vec<_>>()
+//! };
+//! let in_order = [
+//!     ("start", 0), ("end", 0), ("start", 1), ("end", 1), ("start", 2), ("end", 2),
+//! ];
+//!
+//! assert_eq!(block_on(auto::sequence(steps())), [0, 10, 20]);
+//! assert_eq!(log.take(), in_order);
+//! assert_eq!(block_on(manual::sequence(steps())), [0, 10, 20]);
+//! assert_eq!(log.take(), in_order);
+//! ```
+
+pub mod auto {
+    use alloc::vec::Vec;
+    use core::future::Future;
+
+    pub async fn sequence<Fut: Future>(futs: Vec<Fut>) -> Vec<Fut::Output> {
+        let mut outputs = Vec::with_capacity(futs.len());
+        for fut in futs {
+            outputs.push(fut.await);
+        }
+        outputs
+    }
+}
+
+pub mod manual {
+    use alloc::{boxed::Box, vec::Vec};
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    pub async fn sequence<Fut: Future>(futs: Vec<Fut>) -> Vec<Fut::Output> {
+        Sequence {
+            outputs: Some(Vec::with_capacity(futs.len())),
+            futs: futs.into_iter().map(Some).collect::<Box<_>>().into(),
+            current: 0,
+        }
+        .await
+    }
+
+    // pinned as a boxed slice for the same reason as in `join_all`. Each slot
+    // is emptied (dropping its future in place) as soon as the future
+    // completes, rather than all at once at the end.
+    struct Sequence<Fut: Future> {
+        futs: Pin<Box<[Option<Fut>]>>,
+        // the index of the future being run.
+        current: usize,
+        // `None` once handed back as the output.
+        outputs: Option<Vec<Fut::Output>>,
+    }
+
+    // the futures are pinned inside the box, and the outputs are never
+    // pinned at all.
+    impl<Fut: Future> Unpin for Sequence<Fut> {}
+
+    impl<Fut: Future> Sequence<Fut> {
+        fn slot(&mut self, index: usize) -> Option<Pin<&mut Option<Fut>>> {
+            // SAFETY: each slot is pinned in place along with the slice, and
+            // none of them is ever moved out of it.
+            let futs = unsafe { self.futs.as_mut().get_unchecked_mut() };
+            let slot = futs.get_mut(index)?;
+            Some(unsafe { Pin::new_unchecked(slot) })
+        }
+    }
+
+    impl<Fut: Future> Future for Sequence<Fut> {
+        type Output = Vec<Fut::Output>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.get_mut();
+            if this.outputs.is_none() {
+                panic!("`async fn` resumed after completion");
+            }
+            while let Some(mut slot) = this.slot(this.current) {
+                let fut = slot
+                    .as_mut()
+                    .as_pin_mut()
+                    .expect("emptied slots are skipped");
+                let output = ready!(fut.poll(cx));
+                slot.set(None);
+                this.outputs.as_mut().expect("checked above").push(output);
+                this.current += 1;
+            }
+            Poll::Ready(this.outputs.take().expect("checked above"))
+        }
+    }
+}
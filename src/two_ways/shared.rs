@@ -0,0 +1,171 @@
+//! Let several tasks await the same future, each getting its own clone of the
+//! output.
+//!
+//! There is no `auto` version: an `async` block owns whatever it awaits, and
+//! a future can only be awaited by one task at a time. Sharing one takes a
+//! handle type, [`Shared`](manual::Shared), whose clones all point at the same
+//! state. Whichever clone gets polled drives the inner future, using a waker
+//! which wakes every clone still waiting on it.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! use async_intuition::{
+//!     basics::executor::Executor,
+//!     testing::poll_counter,
+//!     two_ways::shared::manual::shared,
+//! };
+//!
+//! let computations = Cell::new(0);
+//! let answer = shared(async {
+//!     // pending once, so that both tasks end up waiting on it.
+//!     poll_counter(1, ()).await;
+//!     computations.set(computations.get() + 1);
+//!     42
+//! });
+//!
+//! let results = [Cell::new(0), Cell::new(0)];
+//! let mut executor = Executor::new();
+//! for result in &results {
+//!     let answer = answer.clone();
+//!     executor.spawn(async move { result.set(answer.await) });
+//! }
+//! assert_eq!(format!("{answer:?}"), "Shared { id: 0, done: false, .. }");
+//! executor.run();
+//!
+//! assert_eq!(results.each_ref().map(Cell::get), [42, 42]);
+//! assert_eq!(computations.get(), 1);
+//! // never polled itself, but the output is there for it all the same.
+//! assert_eq!(format!("{answer:?}"), "Shared { id: 0, done: true, .. }");
+//! ```
+
+pub mod manual {
+    use core::{fmt, future::Future, mem, pin::Pin, task::Poll};
+    use std::{
+        collections::HashMap,
+        sync::{Arc, Mutex},
+        task::{Wake, Waker},
+    };
+
+    pub fn shared<Fut: Future>(fut: Fut) -> Shared<Fut>
+    where
+        Fut::Output: Clone,
+    {
+        Shared {
+            id: 0,
+            inner: Arc::new(Mutex::new(Inner {
+                state: State::Running(Box::pin(fut)),
+                next_id: 1,
+            })),
+            wakers: Arc::default(),
+        }
+    }
+
+    pub struct Shared<Fut: Future> {
+        // identifies this clone's entry in `wakers`.
+        id: usize,
+        inner: Arc<Mutex<Inner<Fut>>>,
+        wakers: Arc<WakeAll>,
+    }
+
+    // prints whether the output is in yet, so neither the future nor its
+    // output has to be `Debug`.
+    impl<Fut: Future> fmt::Debug for Shared<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            let done = matches!(self.inner.lock().unwrap().state, State::Done(_));
+            f.debug_struct("Shared")
+                .field("id", &self.id)
+                .field("done", &done)
+                .finish_non_exhaustive()
+        }
+    }
+
+    struct Inner<Fut: Future> {
+        state: State<Fut>,
+        next_id: usize,
+    }
+
+    enum State<Fut: Future> {
+        // boxed, so the future stays pinned however the state moves around.
+        Running(Pin<Box<Fut>>),
+        Done(Fut::Output),
+    }
+
+    // the waker the inner future is polled with. The inner future only holds
+    // on to the waker from its latest poll, which might belong to a clone
+    // that is later dropped, so every waiting clone has to be woken instead.
+    #[derive(Default)]
+    struct WakeAll {
+        wakers: Mutex<HashMap<usize, Waker>>,
+    }
+
+    impl Wake for WakeAll {
+        fn wake(self: Arc<Self>) {
+            self.wake_by_ref();
+        }
+
+        fn wake_by_ref(self: &Arc<Self>) {
+            // waking with the lock still held would deadlock as soon as a
+            // wake drops a `Shared` clone, or polls one inline.
+            let wakers = mem::take(&mut *self.wakers.lock().unwrap());
+            for (_, waker) in wakers {
+                waker.wake();
+            }
+        }
+    }
+
+    impl<Fut: Future> Clone for Shared<Fut> {
+        fn clone(&self) -> Self {
+            let mut inner = self.inner.lock().unwrap();
+            let id = inner.next_id;
+            inner.next_id += 1;
+            Shared {
+                id,
+                inner: Arc::clone(&self.inner),
+                wakers: Arc::clone(&self.wakers),
+            }
+        }
+    }
+
+    impl<Fut: Future> Drop for Shared<Fut> {
+        fn drop(&mut self) {
+            self.wakers.wakers.lock().unwrap().remove(&self.id);
+        }
+    }
+
+    impl<Fut: Future> Future for Shared<Fut>
+    where
+        Fut::Output: Clone,
+    {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut inner = self.inner.lock().unwrap();
+            let fut = match &mut inner.state {
+                State::Done(output) => return Poll::Ready(output.clone()),
+                State::Running(fut) => fut,
+            };
+
+            // register before polling, so a wakeup which arrives while the
+            // inner future is being polled isn't missed.
+            let mut wakers = self.wakers.wakers.lock().unwrap();
+            wakers
+                .entry(self.id)
+                .and_modify(|waker| waker.clone_from(cx.waker()))
+                .or_insert_with(|| cx.waker().clone());
+            drop(wakers);
+
+            let wake_all = Waker::from(Arc::clone(&self.wakers));
+            let Poll::Ready(output) = fut
+                .as_mut()
+                .poll(&mut core::task::Context::from_waker(&wake_all))
+            else {
+                return Poll::Pending;
+            };
+            inner.state = State::Done(output.clone());
+            drop(inner);
+            // everyone else can pick up the output now.
+            wake_all.wake();
+            Poll::Ready(output)
+        }
+    }
+}
@@ -0,0 +1,150 @@
+//! Two ways for a future to keep hold of its waker between polls, while
+//! whatever is going to wake it (here an [`Event`], standing in for a reactor)
+//! holds on to the other end.
+//!
+//! The [`Waker`] in each poll's context is only borrowed, so to be woken
+//! later, a future has to store a clone of it. [`naive`] stores a fresh clone
+//! on every poll. But a task is usually polled by the same executor with the
+//! same waker each time, and cloning a waker isn't free: at the very least
+//! it's a reference count bumped on one clone and dropped on the other.
+//! [`optimized`] checks [`Waker::will_wake`] first, and only clones when the
+//! waker has actually changed. [`Waker::clone_from`] does the same check
+//! internally, which is what [`Sleep`](crate::basics::Sleep) relies on.
+//!
+//! ```
+//! # use std::pin::pin;
+//! # use std::{future::Future, task::{Context, Poll}};
+//! use async_intuition::{
+//!     testing::RecordingWaker,
+//!     two_ways::shared_state::{naive, optimized, Event},
+//! };
+//!
+//! let recorder = RecordingWaker::new();
+//! let waker = recorder.waker();
+//! let mut cx = Context::from_waker(&waker);
+//!
+//! let event = Event::new();
+//! let mut fut = pin!(naive::wait(&event));
+//! for _ in 0..3 {
+//!     assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+//! }
+//! assert_eq!(recorder.clones(), 3);
+//!
+//! let event = Event::new();
+//! let mut fut = pin!(optimized::wait(&event));
+//! for _ in 0..3 {
+//!     assert_eq!(fut.as_mut().poll(&mut cx), Poll::Pending);
+//! }
+//! assert_eq!(recorder.clones(), 4);
+//!
+//! // a different waker is stored straight away.
+//! let other = RecordingWaker::new();
+//! let other_waker = other.waker();
+//! assert_eq!(fut.as_mut().poll(&mut Context::from_waker(&other_waker)), Poll::Pending);
+//! assert_eq!(other.clones(), 1);
+//!
+//! event.fire();
+//! assert_eq!((recorder.wakes(), other.wakes()), (0, 1));
+//! assert_eq!(fut.as_mut().poll(&mut cx), Poll::Ready(()));
+//! ```
+//!
+//! `benches/shared_state.rs` measures what the skipped clones are worth.
+//!
+//! [`Waker`]: core::task::Waker
+//! [`Waker::will_wake`]: core::task::Waker::will_wake
+//! [`Waker::clone_from`]: core::task::Waker::clone_from
+
+use core::task::Waker;
+use std::sync::{Arc, Mutex};
+
+/// A one-off event, fired from anywhere, which [`naive::Wait`] and
+/// [`optimized::Wait`] wait for.
+#[derive(Clone, Debug, Default)]
+pub struct Event {
+    shared: Arc<Mutex<Shared>>,
+}
+
+#[derive(Debug, Default)]
+struct Shared {
+    fired: bool,
+    // whoever is waiting, as of their last poll.
+    waker: Option<Waker>,
+}
+
+impl Event {
+    pub fn new() -> Self {
+        Self::default()
+    }
+
+    pub fn fire(&self) {
+        let mut shared = self.shared.lock().unwrap();
+        shared.fired = true;
+        if let Some(waker) = shared.waker.take() {
+            waker.wake();
+        }
+    }
+}
+
+pub mod naive {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use super::Event;
+
+    pub fn wait(event: &Event) -> Wait {
+        Wait {
+            event: event.clone(),
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Wait {
+        event: Event,
+    }
+
+    impl Future for Wait {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut shared = self.event.shared.lock().unwrap();
+            if shared.fired {
+                return Poll::Ready(());
+            }
+            shared.waker = Some(cx.waker().clone());
+            Poll::Pending
+        }
+    }
+}
+
+pub mod optimized {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use super::Event;
+
+    pub fn wait(event: &Event) -> Wait {
+        Wait {
+            event: event.clone(),
+        }
+    }
+
+    #[derive(Debug)]
+    pub struct Wait {
+        event: Event,
+    }
+
+    impl Future for Wait {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut shared = self.event.shared.lock().unwrap();
+            if shared.fired {
+                return Poll::Ready(());
+            }
+            match &mut shared.waker {
+                // the stored waker would wake the same task: keep it.
+                Some(waker) if waker.will_wake(cx.waker()) => {}
+                slot => *slot = Some(cx.waker().clone()),
+            }
+            Poll::Pending
+        }
+    }
+}
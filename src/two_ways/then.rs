@@ -0,0 +1,82 @@
+//! Run a future, then feed its output to a closure which produces a second
+//! future to run. Unlike [`a_then_b`](super::a_then_b), a value is carried
+//! across the transition from the first future to the second.
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::then::{auto, manual};
+//!
+//! let double = |n: u32| async move { n * 2 };
+//! assert_eq!(block_on(auto::then(async { 21 }, double)), 42);
+//! assert_eq!(block_on(manual::then(async { 21 }, double)), 42);
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn then<A, F, B>(a: A, f: F) -> B::Output
+    where
+        A: Future,
+        F: FnOnce(A::Output) -> B,
+        B: Future,
+    {
+        f(a.await).await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn then<A, F, B>(a: A, f: F) -> B::Output
+    where
+        A: Future,
+        F: FnOnce(A::Output) -> B,
+        B: Future,
+    {
+        Then::First { a, f: Some(f) }.await
+    }
+
+    #[pin_project(project = ThenProj)]
+    enum Then<A, F, B> {
+        // the closure sits in an `Option` so it can be moved out through the
+        // pinned projection. Dropping `Then` while in this state drops it like
+        // any other field, so no manual drop handling is needed.
+        First {
+            #[pin]
+            a: A,
+            f: Option<F>,
+        },
+        Second {
+            #[pin]
+            b: B,
+        },
+    }
+
+    impl<A, F, B> Future for Then<A, F, B>
+    where
+        A: Future,
+        F: FnOnce(A::Output) -> B,
+        B: Future,
+    {
+        type Output = B::Output;
+
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            loop {
+                match self.as_mut().project() {
+                    ThenProj::First { a, f } => {
+                        let Poll::Ready(out) = a.poll(cx) else {
+                            return Poll::Pending;
+                        };
+                        let f = f.take().expect("`f` is only taken when leaving `First`");
+                        // `set` drops the finished `a` in place before `b`
+                        // takes its spot.
+                        self.set(Then::Second { b: f(out) });
+                    }
+                    ThenProj::Second { b } => return b.poll(cx),
+                }
+            }
+        }
+    }
+}
@@ -0,0 +1,201 @@
+//! Pass events produced by `next` on to `deliver`, at most one per `window`,
+//! until `next` yields `None`. What happens to an event arriving too soon
+//! after the last delivery depends on the [`Policy`]: it is either dropped,
+//! or held back until the window is over.
+//!
+//! ```
+//! # use std::time::{Duration, Instant};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{
+//!     basics::sleep,
+//!     two_ways::throttle::{auto, manual, Policy},
+//! };
+//!
+//! // event `i` arrives `gaps[i]` milliseconds after `next` is called for it.
+//! let events = |gaps: &'static [u64]| {
+//!     let mut gaps = gaps.iter().enumerate();
+//!     move || {
+//!         let next = gaps.next();
+//!         async move {
+//!             let (i, &gap) = next?;
+//!             sleep(Duration::from_millis(gap)).await;
+//!             Some(i)
+//!         }
+//!     }
+//! };
+//! let window = Duration::from_millis(100);
+//! // the second event comes within the first one's window, the third doesn't.
+//! let gaps = &[0, 10, 300];
+//!
+//! for policy in [Policy::Drop, Policy::Delay] {
+//!     let mut delivered = Vec::new();
+//!     let deliver = |event| delivered.push((event, Instant::now()));
+//!     block_on(auto::throttle(events(gaps), window, policy, deliver));
+//!     let mut manual_delivered = Vec::new();
+//!     let deliver = |event| manual_delivered.push((event, Instant::now()));
+//!     block_on(manual::throttle(events(gaps), window, policy, deliver));
+//!
+//!     for delivered in [delivered, manual_delivered] {
+//!         let events: Vec<_> = delivered.iter().map(|&(event, _)| event).collect();
+//!         match policy {
+//!             Policy::Drop => assert_eq!(events, [0, 2]),
+//!             Policy::Delay => assert_eq!(events, [0, 1, 2]),
+//!         }
+//!         for pair in delivered.windows(2) {
+//!             assert!(pair[1].1 - pair[0].1 >= window);
+//!         }
+//!     }
+//! }
+//! ```
+
+/// What to do with an event which arrives while the window opened by the
+/// last delivery is still running.
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Policy {
+    /// Throw the event away.
+    Drop,
+    /// Deliver the event once the window is over. No further events are
+    /// asked for in the meantime.
+    Delay,
+}
+
+pub mod auto {
+    use std::{
+        future::Future,
+        time::{Duration, Instant},
+    };
+
+    use super::Policy;
+    use crate::basics::Sleep;
+
+    pub async fn throttle<T, EventFut, GetNextFut, Deliver>(
+        mut next: GetNextFut,
+        window: Duration,
+        policy: Policy,
+        mut deliver: Deliver,
+    ) where
+        EventFut: Future<Output = Option<T>>,
+        GetNextFut: FnMut() -> EventFut,
+        Deliver: FnMut(T),
+    {
+        let mut last_emit: Option<Instant> = None;
+        while let Some(event) = next().await {
+            if let Some(last_emit) = last_emit {
+                let window_end = last_emit + window;
+                if Instant::now() < window_end {
+                    match policy {
+                        Policy::Drop => continue,
+                        Policy::Delay => Sleep::at(window_end).await,
+                    }
+                }
+            }
+            deliver(event);
+            last_emit = Some(Instant::now());
+        }
+    }
+}
+
+pub mod manual {
+    use std::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+        time::{Duration, Instant},
+    };
+
+    use pin_project::pin_project;
+
+    use super::Policy;
+    use crate::basics::Sleep;
+
+    pub async fn throttle<T, EventFut, GetNextFut, Deliver>(
+        next: GetNextFut,
+        window: Duration,
+        policy: Policy,
+        deliver: Deliver,
+    ) where
+        EventFut: Future<Output = Option<T>>,
+        GetNextFut: FnMut() -> EventFut,
+        Deliver: FnMut(T),
+    {
+        Throttle {
+            window,
+            policy,
+            next,
+            deliver,
+            event_fut: None,
+            last_emit: None,
+            held: None,
+            sleep: None,
+            done: false,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Throttle<T, EventFut, GetNextFut, Deliver> {
+        window: Duration,
+        policy: Policy,
+        next: GetNextFut,
+        deliver: Deliver,
+        #[pin]
+        event_fut: Option<EventFut>,
+        last_emit: Option<Instant>,
+        // with `Policy::Delay`, an event which came too early waits here
+        // until `sleep` reaches the end of the window.
+        held: Option<T>,
+        #[pin]
+        sleep: Option<Sleep>,
+        done: bool,
+    }
+
+    impl<T, EventFut, GetNextFut, Deliver> Future for Throttle<T, EventFut, GetNextFut, Deliver>
+    where
+        EventFut: Future<Output = Option<T>>,
+        GetNextFut: FnMut() -> EventFut,
+        Deliver: FnMut(T),
+    {
+        type Output = ();
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+
+            loop {
+                if let Some(sleep) = this.sleep.as_mut().as_pin_mut() {
+                    ready!(sleep.poll(cx));
+                    this.sleep.set(None);
+                    (this.deliver)(this.held.take().expect("held while sleeping"));
+                    *this.last_emit = Some(Instant::now());
+                }
+
+                if this.event_fut.is_none() {
+                    this.event_fut.set(Some((this.next)()));
+                }
+                let event_fut = this.event_fut.as_mut().as_pin_mut().expect("set above");
+                let Some(event) = ready!(event_fut.poll(cx)) else {
+                    *this.done = true;
+                    return Poll::Ready(());
+                };
+                this.event_fut.set(None);
+
+                let window_end = this.last_emit.map(|last_emit| last_emit + *this.window);
+                match window_end {
+                    Some(window_end) if Instant::now() < window_end => match this.policy {
+                        Policy::Drop => {}
+                        Policy::Delay => {
+                            *this.held = Some(event);
+                            this.sleep.set(Some(Sleep::at(window_end)));
+                        }
+                    },
+                    _ => {
+                        (this.deliver)(event);
+                        *this.last_emit = Some(Instant::now());
+                    }
+                }
+            }
+        }
+    }
+}
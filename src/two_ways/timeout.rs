@@ -0,0 +1,153 @@
+//! Run a future, giving up if it hasn't completed within a duration.
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use futures_lite::future::{block_on, pending};
+//! use async_intuition::two_ways::timeout::{auto, manual, Elapsed};
+//!
+//! let long = Duration::from_secs(60);
+//! assert_eq!(block_on(auto::timeout(long, async { 1 })), Ok(1));
+//! assert_eq!(block_on(manual::timeout(long, async { 1 })), Ok(1));
+//!
+//! let short = Duration::from_millis(10);
+//! assert_eq!(block_on(auto::timeout(short, pending::<()>())), Err(Elapsed));
+//! assert_eq!(block_on(manual::timeout(short, pending::<()>())), Err(Elapsed));
+//! ```
+//!
+//! [`Timeout`] holds the same two arguments without starting anything. It
+//! implements [`IntoFuture`], so `.await` turns it into the timeout future,
+//! the same way `.await` on a plain future calls `into_future` and gets the
+//! future itself back. That lets builder-style APIs hand out something
+//! which can be configured further and then awaited directly:
+//!
+//! ```
+//! # use std::time::Duration;
+//! # use futures_lite::future::{block_on, pending};
+//! use async_intuition::two_ways::timeout::{Elapsed, Timeout};
+//!
+//! let long = Duration::from_secs(60);
+//! let short = Duration::from_millis(10);
+//! assert_eq!(block_on(async { Timeout::new(long, async { 1 }).await }), Ok(1));
+//! // the duration can still be changed, up until the builder is awaited.
+//! let builder = Timeout::new(long, pending::<()>()).duration(short);
+//! assert_eq!(block_on(async { builder.await }), Err(Elapsed));
+//! ```
+
+use core::{fmt, future::IntoFuture, time::Duration};
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub struct Elapsed;
+
+impl fmt::Display for Elapsed {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.write_str("deadline has elapsed")
+    }
+}
+
+impl core::error::Error for Elapsed {}
+
+/// A future and how long to give it, waiting to be awaited.
+pub struct Timeout<Fut> {
+    dur: Duration,
+    fut: Fut,
+}
+
+impl<Fut> fmt::Debug for Timeout<Fut> {
+    fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+        f.debug_struct("Timeout")
+            .field("dur", &self.dur)
+            .finish_non_exhaustive()
+    }
+}
+
+impl<Fut> Timeout<Fut> {
+    pub fn new(dur: Duration, fut: Fut) -> Self {
+        Timeout { dur, fut }
+    }
+
+    /// Replace the duration to give the future.
+    pub fn duration(self, dur: Duration) -> Self {
+        Timeout { dur, ..self }
+    }
+}
+
+impl<Fut: IntoFuture> IntoFuture for Timeout<Fut> {
+    type Output = Result<Fut::Output, Elapsed>;
+    type IntoFuture = manual::TimeoutFuture<Fut::IntoFuture>;
+
+    fn into_future(self) -> Self::IntoFuture {
+        // the clock only starts once the timeout is actually awaited.
+        manual::TimeoutFuture::new(self.dur, self.fut.into_future())
+    }
+}
+
+pub mod auto {
+    use core::{future::Future, time::Duration};
+
+    use super::Elapsed;
+    use crate::basics::sleep;
+
+    pub async fn timeout<Fut: Future>(dur: Duration, fut: Fut) -> Result<Fut::Output, Elapsed> {
+        futures_lite::future::or(async { Ok(fut.await) }, async {
+            sleep(dur).await;
+            Err(Elapsed)
+        })
+        .await
+    }
+}
+
+pub mod manual {
+    use core::{fmt, future::Future, pin::Pin, task::Poll, time::Duration};
+
+    use pin_project::pin_project;
+
+    use super::Elapsed;
+    use crate::basics::{sleep, Sleep};
+
+    pub async fn timeout<Fut: Future>(dur: Duration, fut: Fut) -> Result<Fut::Output, Elapsed> {
+        TimeoutFuture::new(dur, fut).await
+    }
+
+    /// What awaiting a [`Timeout`](super::Timeout) builder runs.
+    #[pin_project]
+    pub struct TimeoutFuture<Fut> {
+        #[pin]
+        fut: Fut,
+        #[pin]
+        sleep: Sleep,
+    }
+
+    impl<Fut> TimeoutFuture<Fut> {
+        pub(super) fn new(dur: Duration, fut: Fut) -> Self {
+            TimeoutFuture {
+                fut,
+                sleep: sleep(dur),
+            }
+        }
+    }
+
+    impl<Fut> fmt::Debug for TimeoutFuture<Fut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("TimeoutFuture")
+                .field("sleep", &self.sleep)
+                .finish_non_exhaustive()
+        }
+    }
+
+    impl<Fut: Future> Future for TimeoutFuture<Fut> {
+        type Output = Result<Fut::Output, Elapsed>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            // the inner future gets the first chance, so a future which
+            // completes right at the deadline still counts as on time.
+            if let Poll::Ready(out) = this.fut.poll(cx) {
+                return Poll::Ready(Ok(out));
+            }
+            match this.sleep.poll(cx) {
+                Poll::Ready(()) => Poll::Ready(Err(Elapsed)),
+                Poll::Pending => Poll::Pending,
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! Run a future, giving up if it hasn't completed by a deadline. Unlike
+//! [`timeout`](super::timeout), the deadline is an [`Instant`], so several
+//! operations in a row can share one deadline without each restarting the
+//! clock.
+//!
+//! The deadline is checked before the inner future is polled, so one which
+//! has already passed fails straight away, without the inner future ever
+//! getting a chance to run.
+//!
+//! ```
+//! # use std::time::{Duration, Instant};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::{timeout::Elapsed, timeout_at::{auto, manual}};
+//!
+//! let deadline = Instant::now() + Duration::from_secs(60);
+//! assert_eq!(block_on(auto::timeout_at(deadline, async { 1 })), Ok(1));
+//! assert_eq!(block_on(manual::timeout_at(deadline, async { 2 })), Ok(2));
+//!
+//! let past = Instant::now();
+//! let never_polled = || async { unreachable!("polled after the deadline") };
+//! assert_eq!(block_on(auto::timeout_at(past, never_polled())), Err::<(), _>(Elapsed));
+//! assert_eq!(block_on(manual::timeout_at(past, never_polled())), Err::<(), _>(Elapsed));
+//! ```
+//!
+//! [`Instant`]: std::time::Instant
+
+pub mod auto {
+    use std::{future::Future, time::Instant};
+
+    use crate::{basics::Sleep, two_ways::timeout::Elapsed};
+
+    pub async fn timeout_at<Fut: Future>(
+        deadline: Instant,
+        fut: Fut,
+    ) -> Result<Fut::Output, Elapsed> {
+        // `or` polls its first future first.
+        futures_lite::future::or(
+            async {
+                Sleep::at(deadline).await;
+                Err(Elapsed)
+            },
+            async { Ok(fut.await) },
+        )
+        .await
+    }
+}
+
+pub mod manual {
+    use std::{future::Future, pin::Pin, task::Poll, time::Instant};
+
+    use pin_project::pin_project;
+
+    use crate::{basics::Sleep, two_ways::timeout::Elapsed};
+
+    pub async fn timeout_at<Fut: Future>(
+        deadline: Instant,
+        fut: Fut,
+    ) -> Result<Fut::Output, Elapsed> {
+        TimeoutAt {
+            fut,
+            sleep: Sleep::at(deadline),
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct TimeoutAt<Fut> {
+        #[pin]
+        fut: Fut,
+        #[pin]
+        sleep: Sleep,
+    }
+
+    impl<Fut: Future> Future for TimeoutAt<Fut> {
+        type Output = Result<Fut::Output, Elapsed>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            // the other way round from `timeout`: a deadline which has
+            // passed wins, even against a future which is ready.
+            if this.sleep.poll(cx).is_ready() {
+                return Poll::Ready(Err(Elapsed));
+            }
+            this.fut.poll(cx).map(Ok)
+        }
+    }
+}
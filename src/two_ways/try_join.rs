@@ -0,0 +1,121 @@
+//! Run two fallible futures concurrently, resolving to both their successes,
+//! or to the first error as soon as there is one.
+//!
+//! Where [`join`](super::join) always waits for both sides, this gives up on
+//! the first failure: the other side is dropped there and then, even if it is
+//! still in flight, since its output no longer matters.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use futures_lite::future::{block_on, pending};
+//! use async_intuition::{
+//!     testing::{poll_counter, poll_once},
+//!     two_ways::{on_drop::auto::on_drop, try_join::{auto, manual}},
+//! };
+//!
+//! type R = Result<u8, &'static str>;
+//!
+//! // both succeed.
+//! assert_eq!(
+//!     block_on(auto::try_join(poll_counter(2, R::Ok(1)), poll_counter(1, R::Ok(2)))),
+//!     Ok((1, 2)),
+//! );
+//! assert_eq!(
+//!     block_on(manual::try_join(poll_counter(2, R::Ok(1)), poll_counter(1, R::Ok(2)))),
+//!     Ok((1, 2)),
+//! );
+//!
+//! // one side fails early, while the other would never finish.
+//! let never = || pending::<R>();
+//! assert_eq!(block_on(auto::try_join(poll_counter(1, R::Err("left")), never())), Err("left"));
+//! assert_eq!(block_on(manual::try_join(poll_counter(1, R::Err("left")), never())), Err("left"));
+//! assert_eq!(block_on(auto::try_join(never(), poll_counter(1, R::Err("right")))), Err("right"));
+//! assert_eq!(block_on(manual::try_join(never(), poll_counter(1, R::Err("right")))), Err("right"));
+//!
+//! // the sibling goes as soon as the error is in, not when the join is dropped.
+//! let dropped = Cell::new(false);
+//! let sibling = on_drop(never(), || dropped.set(true));
+//! let mut fut = Box::pin(manual::try_join(poll_counter(1, R::Err("left")), sibling));
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert!(!dropped.get());
+//! assert_eq!(poll_once(fut.as_mut()), std::task::Poll::Ready(Err("left")));
+//! assert!(dropped.get());
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn try_join<T, U, E, A, B>(a: A, b: B) -> Result<(T, U), E>
+    where
+        A: Future<Output = Result<T, E>>,
+        B: Future<Output = Result<U, E>>,
+    {
+        futures_lite::future::try_zip(a, b).await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    use crate::two_ways::maybe_done::manual::{maybe_done, MaybeDone};
+
+    pub async fn try_join<T, U, E, A, B>(a: A, b: B) -> Result<(T, U), E>
+    where
+        A: Future<Output = Result<T, E>>,
+        B: Future<Output = Result<U, E>>,
+    {
+        TryJoin {
+            a: maybe_done(a),
+            b: maybe_done(b),
+        }
+        .await
+    }
+
+    // both sides end up `Gone` once this resolves: taken on success, and
+    // dropped in place on failure.
+    #[pin_project]
+    struct TryJoin<A: Future, B: Future> {
+        #[pin]
+        a: MaybeDone<A>,
+        #[pin]
+        b: MaybeDone<B>,
+    }
+
+    impl<T, U, E, A, B> Future for TryJoin<A, B>
+    where
+        A: Future<Output = Result<T, E>>,
+        B: Future<Output = Result<U, E>>,
+    {
+        type Output = Result<(T, U), E>;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            if let (MaybeDone::Gone, MaybeDone::Gone) = (&*this.a, &*this.b) {
+                panic!("`async fn` resumed after completion");
+            }
+
+            let a_ready = this.a.as_mut().poll(cx).is_ready();
+            if let MaybeDone::Done { output: Err(_) } = &*this.a {
+                // dropping the other side in place is fine while pinned.
+                this.b.set(MaybeDone::Gone);
+                return Poll::Ready(Err(this.a.take_output().unwrap().err().unwrap()));
+            }
+            let b_ready = this.b.as_mut().poll(cx).is_ready();
+            if let MaybeDone::Done { output: Err(_) } = &*this.b {
+                this.a.set(MaybeDone::Gone);
+                return Poll::Ready(Err(this.b.take_output().unwrap().err().unwrap()));
+            }
+
+            if a_ready && b_ready {
+                // both are `Ok`, or we would have returned above.
+                let a = this.a.take_output().unwrap().ok().unwrap();
+                let b = this.b.take_output().unwrap().ok().unwrap();
+                Poll::Ready(Ok((a, b)))
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+}
@@ -0,0 +1,87 @@
+//! [`join`](super::join) for two to eight futures of different types,
+//! resolving to a tuple of their outputs.
+//!
+//! Each arity needs its own struct, since a struct can't have a variable
+//! number of fields. Rather than writing eight nearly identical futures by
+//! hand, a `macro_rules!` stamps them out from a list of names. This is how
+//! libraries provide `join!`-style combinators for every size of tuple.
+//!
+//! There is no `auto` version: `async` can only nest two-way joins inside one
+//! another, which is exactly the boilerplate the macro exists to avoid.
+//!
+//! ```
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::tuple_join::manual::{join3, join5};
+//!
+//! async fn slow<T>(value: T) -> T {
+//!     yield_now().await;
+//!     value
+//! }
+//!
+//! assert_eq!(block_on(join3(slow(1), async { "two" }, slow(3.0))), (1, "two", 3.0));
+//! assert_eq!(
+//!     block_on(join5(async { 'a' }, slow(2u8), slow("c"), async { [4] }, slow(Some(5)))),
+//!     ('a', 2, "c", [4], Some(5)),
+//! );
+//! ```
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    macro_rules! tuple_join {
+        ($($join:ident $Join:ident ($($Fut:ident $fut:ident $out:ident),+);)+) => {$(
+            // one argument per future, however many that takes.
+            #[allow(clippy::too_many_arguments)]
+            pub async fn $join<$($Fut: Future),+>($($fut: $Fut),+) -> ($($Fut::Output,)+) {
+                $Join { $($fut, $out: None),+ }.await
+            }
+
+            #[pin_project]
+            struct $Join<$($Fut: Future),+> {
+                $(
+                    #[pin]
+                    $fut: $Fut,
+                    // `Some` once the future has finished, after which it
+                    // must not be polled again.
+                    $out: Option<$Fut::Output>,
+                )+
+            }
+
+            impl<$($Fut: Future),+> Future for $Join<$($Fut),+> {
+                type Output = ($($Fut::Output,)+);
+
+                fn poll(
+                    self: Pin<&mut Self>,
+                    cx: &mut core::task::Context<'_>,
+                ) -> Poll<Self::Output> {
+                    let this = self.project();
+                    let mut all_done = true;
+                    $(
+                        if this.$out.is_none() {
+                            match this.$fut.poll(cx) {
+                                Poll::Ready(out) => *this.$out = Some(out),
+                                Poll::Pending => all_done = false,
+                            }
+                        }
+                    )+
+                    if !all_done {
+                        return Poll::Pending;
+                    }
+                    Poll::Ready(($(this.$out.take().expect("every future is done"),)+))
+                }
+            }
+        )+};
+    }
+
+    tuple_join! {
+        join2 Join2 (A a a_out, B b b_out);
+        join3 Join3 (A a a_out, B b b_out, C c c_out);
+        join4 Join4 (A a a_out, B b b_out, C c c_out, D d d_out);
+        join5 Join5 (A a a_out, B b b_out, C c c_out, D d d_out, E e e_out);
+        join6 Join6 (A a a_out, B b b_out, C c c_out, D d d_out, E e e_out, F f f_out);
+        join7 Join7 (A a a_out, B b b_out, C c c_out, D d d_out, E e e_out, F f f_out, G g g_out);
+        join8 Join8 (A a a_out, B b b_out, C c c_out, D d d_out, E e e_out, F f f_out, G g g_out, H h h_out);
+    }
+}
@@ -0,0 +1,108 @@
+//! Thread a state through a sequence of futures: the closure looks at the
+//! current state and either returns a future producing the next state, or
+//! `None` to stop with the state as it is.
+//!
+//! [`repeat`](super::repeat) and [`until_equals`](super::until_equals) are
+//! both this loop with the state fixed in advance (a countdown, the last
+//! value seen).
+//!
+//! ```
+//! # use futures_lite::future::{block_on, yield_now};
+//! use async_intuition::two_ways::unfold::{auto, manual};
+//!
+//! // 0 marks the end of the sequence.
+//! let numbers = [3, 4, 5, 0, 6];
+//! let fetch = |i: usize| async move {
+//!     yield_now().await;
+//!     numbers[i]
+//! };
+//!
+//! // (index of the next number, sum so far, whether the end was reached)
+//! let step = |&(i, sum, ended): &(usize, u32, bool)| {
+//!     (!ended).then(|| async move {
+//!         let n = fetch(i).await;
+//!         (i + 1, sum + n, n == 0)
+//!     })
+//! };
+//!
+//! assert_eq!(block_on(auto::unfold((0, 0, false), step)), (4, 12, true));
+//! assert_eq!(block_on(manual::unfold((0, 0, false), step)), (4, 12, true));
+//! assert_eq!(block_on(manual::unfold((0, 0, true), step)), (0, 0, true));
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn unfold<S, F, Fut>(init: S, mut f: F) -> S
+    where
+        F: FnMut(&S) -> Option<Fut>,
+        Fut: Future<Output = S>,
+    {
+        let mut state = init;
+        while let Some(fut) = f(&state) {
+            state = fut.await;
+        }
+        state
+    }
+}
+
+pub mod manual {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{ready, Poll},
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn unfold<S, F, Fut>(init: S, f: F) -> S
+    where
+        F: FnMut(&S) -> Option<Fut>,
+        Fut: Future<Output = S>,
+    {
+        Unfold {
+            state: Some(init),
+            f,
+            fut: None,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Unfold<S, F, Fut> {
+        // `None` once handed back as the output.
+        state: Option<S>,
+        f: F,
+        // the future producing the next state, if one is in flight.
+        #[pin]
+        fut: Option<Fut>,
+    }
+
+    impl<S, F, Fut> Future for Unfold<S, F, Fut>
+    where
+        F: FnMut(&S) -> Option<Fut>,
+        Fut: Future<Output = S>,
+    {
+        type Output = S;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            loop {
+                if let Some(fut) = this.fut.as_mut().as_pin_mut() {
+                    let next = ready!(fut.poll(cx));
+                    this.fut.set(None);
+                    *this.state = Some(next);
+                }
+
+                let state = this
+                    .state
+                    .as_ref()
+                    .expect("`async fn` resumed after completion");
+                match (this.f)(state) {
+                    Some(fut) => this.fut.set(Some(fut)),
+                    None => return Poll::Ready(this.state.take().expect("checked above")),
+                }
+            }
+        }
+    }
+}
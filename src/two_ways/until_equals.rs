@@ -1,3 +1,77 @@
+//! Keep awaiting numbers produced by `next` until one equals `check`.
+//!
+//! A mismatch must not stall the manual version: `block_on` parks the thread
+//! until woken, so this only returns if the next number is fetched right away.
+//!
+//! `manual` does that with a loop, emptying `num_fut` after each number and
+//! filling it again at the top. `manual_opt` swaps the next future straight
+//! in and recurses through `poll_next` instead, hoping for a tail call. That
+//! only happens with optimizations on: in a debug build every mismatch in a
+//! row costs a stack frame, so a long enough run of them overflows the stack.
+//!
+//! ```
+//! # use std::cell::Cell;
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::until_equals::{auto, manual, manual_opt};
+//!
+//! let counter = Cell::new(0);
+//! let next = || async {
+//!     counter.set(counter.get() + 1);
+//!     counter.get()
+//! };
+//!
+//! block_on(auto::until_equals(3, next));
+//! assert_eq!(counter.get(), 3);
+//! block_on(manual::until_equals(6, next));
+//! assert_eq!(counter.get(), 6);
+//! block_on(manual_opt::until_equals(9, next));
+//! assert_eq!(counter.get(), 9);
+//! ```
+//!
+//! `next` is an `Fn`, so any state it keeps between calls has to go through
+//! something like the `Cell` above. The `_mut` siblings take an `FnMut`
+//! instead, which can just mutate what it captures:
+//!
+//! ```
+//! # use futures_lite::future::block_on;
+//! use async_intuition::two_ways::until_equals::{auto, manual};
+//!
+//! let mut calls = 0;
+//! let mut next = || {
+//!     // yields 0, 1, 2, ... on successive calls.
+//!     let num = calls;
+//!     calls += 1;
+//!     async move { num }
+//! };
+//! block_on(auto::until_equals_mut(2, &mut next));
+//! block_on(manual::until_equals_mut(5, &mut next));
+//! assert_eq!(calls, 6);
+//! ```
+//!
+//! [`manual::UntilEquals`] is also a [`FusedFuture`], so whoever polls it
+//! can tell once it has found its number. Its `Debug` output shows whether
+//! it is waiting on a number right now:
+//!
+//! ```
+//! # use std::pin::pin;
+//! use async_intuition::{
+//!     testing::{poll_counter, poll_once},
+//!     two_ways::{fuse::FusedFuture, until_equals::manual::UntilEquals},
+//! };
+//!
+//! let mut nums = [1, 2, 3].into_iter();
+//! let mut fut = pin!(UntilEquals::new(3, || poll_counter(1, nums.next().unwrap())));
+//! assert_eq!(format!("{fut:?}"), "UntilEquals { check: 3, waiting: false, .. }");
+//! assert!(poll_once(fut.as_mut()).is_pending());
+//! assert_eq!(format!("{fut:?}"), "UntilEquals { check: 3, waiting: true, .. }");
+//! while !fut.is_terminated() {
+//!     let _ = poll_once(fut.as_mut());
+//! }
+//! assert_eq!(nums.next(), None);
+//! ```
+//!
+//! [`FusedFuture`]: crate::two_ways::fuse::FusedFuture
+
 pub mod auto {
     use core::future::Future;
 
@@ -13,14 +87,126 @@ pub mod auto {
             }
         }
     }
+
+    pub async fn until_equals_mut<NumFut, GetNextFut>(check: u32, mut next: GetNextFut)
+    where
+        NumFut: Future<Output = u32>,
+        GetNextFut: FnMut() -> NumFut,
+    {
+        loop {
+            let num = next().await;
+            if num == check {
+                return;
+            }
+        }
+    }
 }
 
 pub mod manual {
-    use core::{
-        future::Future,
-        pin::{pin, Pin},
-        task::Poll,
-    };
+    use core::{fmt, future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    use crate::two_ways::fuse::FusedFuture;
+
+    pub async fn until_equals<NumFut, GetNextFut>(check: u32, next: GetNextFut)
+    where
+        NumFut: Future<Output = u32>,
+        GetNextFut: Fn() -> NumFut,
+    {
+        UntilEquals::new(check, next).await
+    }
+
+    pub async fn until_equals_mut<NumFut, GetNextFut>(check: u32, next: GetNextFut)
+    where
+        NumFut: Future<Output = u32>,
+        GetNextFut: FnMut() -> NumFut,
+    {
+        UntilEquals::new(check, next).await
+    }
+
+    #[pin_project]
+    pub struct UntilEquals<NumFut, GetNextFut> {
+        check: u32,
+        next: GetNextFut,
+        #[pin]
+        num_fut: Option<NumFut>,
+        done: bool,
+    }
+
+    impl<NumFut, GetNextFut> UntilEquals<NumFut, GetNextFut> {
+        /// The future behind [`until_equals`] and [`until_equals_mut`], for
+        /// when it needs to be asked whether it has finished.
+        pub fn new(check: u32, next: GetNextFut) -> Self {
+            UntilEquals {
+                check,
+                next,
+                num_fut: None,
+                done: false,
+            }
+        }
+    }
+
+    impl<NumFut, GetNextFut> fmt::Debug for UntilEquals<NumFut, GetNextFut> {
+        fn fmt(&self, f: &mut fmt::Formatter<'_>) -> fmt::Result {
+            f.debug_struct("UntilEquals")
+                .field("check", &self.check)
+                .field("waiting", &self.num_fut.is_some())
+                .finish_non_exhaustive()
+        }
+    }
+
+    // `FnMut` covers both flavours. `next` isn't `#[pin]`, so projecting
+    // gives a plain `&mut GetNextFut`, which is all calling an `FnMut` takes.
+    impl<NumFut, GetNextFut> Future for UntilEquals<NumFut, GetNextFut>
+    where
+        NumFut: Future<Output = u32>,
+        GetNextFut: FnMut() -> NumFut,
+    {
+        type Output = ();
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+
+            // a mismatch must go straight on to the next number. Returning
+            // `Pending` there would leave nothing to wake the task.
+            loop {
+                let num_fut = if let Some(fut) = this.num_fut.as_mut().as_pin_mut() {
+                    fut
+                } else {
+                    this.num_fut.set(Some((this.next)()));
+                    unsafe { this.num_fut.as_mut().as_pin_mut().unwrap_unchecked() }
+                };
+
+                match num_fut.poll(cx) {
+                    Poll::Ready(num) => {
+                        this.num_fut.set(None);
+                        if num == *this.check {
+                            *this.done = true;
+                            return Poll::Ready(());
+                        }
+                    }
+                    Poll::Pending => return Poll::Pending,
+                }
+            }
+        }
+    }
+
+    impl<NumFut, GetNextFut> FusedFuture for UntilEquals<NumFut, GetNextFut>
+    where
+        NumFut: Future<Output = u32>,
+        GetNextFut: FnMut() -> NumFut,
+    {
+        fn is_terminated(&self) -> bool {
+            self.done
+        }
+    }
+}
+
+pub mod manual_opt {
+    use core::{future::Future, pin::Pin, task::Poll};
 
     use pin_project::pin_project;
 
@@ -33,6 +219,7 @@ pub mod manual {
             check,
             next,
             num_fut: None,
+            done: false,
         }
         .await
     }
@@ -43,35 +230,63 @@ pub mod manual {
         next: GetNextFut,
         #[pin]
         num_fut: Option<NumFut>,
+        done: bool,
     }
 
-    impl<NumFut, GetNextFut> Future for UntilEquals<NumFut, GetNextFut>
+    impl<NumFut, GetNextFut> UntilEquals<NumFut, GetNextFut>
     where
         NumFut: Future<Output = u32>,
         GetNextFut: Fn() -> NumFut,
     {
-        type Output = ();
-        fn poll(self: Pin<&mut Self>, cx: &mut std::task::Context<'_>) -> Poll<Self::Output> {
-            let mut this = self.project();
-
-            let num_fut = if let Some(fut) = this.num_fut.as_mut().as_pin_mut() {
-                fut
-            } else {
-                this.num_fut.set(Some((this.next)()));
-                unsafe { this.num_fut.as_mut().as_pin_mut().unwrap_unchecked() }
-            };
+        /// # Safety
+        ///
+        /// `num_fut` must be `Some`.
+        unsafe fn poll_next(
+            mut self: Pin<&mut Self>,
+            cx: &mut core::task::Context<'_>,
+        ) -> Poll<()> {
+            let mut this = self.as_mut().project();
+            let num_fut = unsafe { this.num_fut.as_mut().as_pin_mut().unwrap_unchecked() };
 
             match num_fut.poll(cx) {
-                Poll::Ready(num) => {
+                Poll::Ready(num) if num == *this.check => {
                     this.num_fut.set(None);
-                    if num == *this.check {
-                        Poll::Ready(())
-                    } else {
-                        Poll::Pending
-                    }
+                    *this.done = true;
+                    Poll::Ready(())
+                }
+                Poll::Ready(_) => {
+                    // replace the finished future directly, skipping the
+                    // `None` round trip (and its check) that `manual` makes.
+                    this.num_fut.set(Some((this.next)()));
+                    // Rust makes no promise to turn this into a jump. Nothing
+                    // is left to drop after the call, so LLVM will usually
+                    // rewrite the self-recursion into a loop with
+                    // optimizations on, but a debug build pushes a stack
+                    // frame per mismatch and a long enough run of them will
+                    // overflow the stack.
+                    unsafe { self.poll_next(cx) }
                 }
                 Poll::Pending => Poll::Pending,
             }
         }
     }
+
+    impl<NumFut, GetNextFut> Future for UntilEquals<NumFut, GetNextFut>
+    where
+        NumFut: Future<Output = u32>,
+        GetNextFut: Fn() -> NumFut,
+    {
+        type Output = ();
+        fn poll(mut self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let mut this = self.as_mut().project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+            if this.num_fut.is_none() {
+                this.num_fut.set(Some((this.next)()));
+            }
+            // SAFETY: `num_fut` was filled in just above if it was empty.
+            unsafe { self.poll_next(cx) }
+        }
+    }
 }
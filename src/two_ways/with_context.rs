@@ -0,0 +1,82 @@
+//! Call `f` with the [`Waker`] from each poll's [`Context`], then pass the
+//! poll on to the inner future.
+//!
+//! There is no `auto` version, and that is the point of this one: `async`
+//! code never gets to see the `Context` it is being polled with. The compiler
+//! threads it through every `.await` down to the leaf futures, which are the
+//! only ones that need it. A combinator which wants to look at the waker has
+//! to sit in that chain as a hand-written [`Future`].
+//!
+//! Nothing promises the same waker on every poll. A future which moves to a
+//! different executor, or just a different task, gets a different one, which
+//! is why leaf futures check [`Waker::will_wake`] before reusing the waker
+//! they stored last time:
+//!
+//! ```
+//! # use std::{cell::RefCell, future::Future, pin::pin, task::{Context, Waker}};
+//! use async_intuition::{
+//!     testing::{poll_counter, RecordingWaker},
+//!     two_ways::with_context::manual::with_context,
+//! };
+//!
+//! let seen: RefCell<Vec<Waker>> = RefCell::default();
+//! let mut fut = pin!(with_context(poll_counter(2, ()), |waker: &Waker| {
+//!     let mut seen = seen.borrow_mut();
+//!     if !seen.iter().any(|w| w.will_wake(waker)) {
+//!         seen.push(waker.clone());
+//!     }
+//! }));
+//!
+//! let first = RecordingWaker::new().waker();
+//! let second = RecordingWaker::new().waker();
+//! assert!(fut.as_mut().poll(&mut Context::from_waker(&first)).is_pending());
+//! assert!(fut.as_mut().poll(&mut Context::from_waker(&first)).is_pending());
+//! assert_eq!(seen.borrow().len(), 1);
+//! // as if the future had been handed over to another executor.
+//! assert!(fut.as_mut().poll(&mut Context::from_waker(&second)).is_ready());
+//! assert_eq!(seen.borrow().len(), 2);
+//! ```
+//!
+//! [`Context`]: core::task::Context
+//! [`Future`]: core::future::Future
+//! [`Waker`]: core::task::Waker
+//! [`Waker::will_wake`]: core::task::Waker::will_wake
+
+pub mod manual {
+    use core::{
+        future::Future,
+        pin::Pin,
+        task::{Poll, Waker},
+    };
+
+    use pin_project::pin_project;
+
+    pub async fn with_context<Fut, F>(fut: Fut, f: F) -> Fut::Output
+    where
+        Fut: Future,
+        F: FnMut(&Waker),
+    {
+        WithContext { fut, f }.await
+    }
+
+    #[pin_project]
+    struct WithContext<Fut, F> {
+        #[pin]
+        fut: Fut,
+        f: F,
+    }
+
+    impl<Fut, F> Future for WithContext<Fut, F>
+    where
+        Fut: Future,
+        F: FnMut(&Waker),
+    {
+        type Output = Fut::Output;
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            (this.f)(cx.waker());
+            this.fut.poll(cx)
+        }
+    }
+}
@@ -0,0 +1,119 @@
+//! Run a future while an [`Interval`](crate::basics::interval::Interval)
+//! ticks alongside it, calling back on every tick until the future completes.
+//! Resolves to the future's output along with how many ticks there were.
+//!
+//! Two very different sub-futures are polled side by side here: one which
+//! completes once, and one which completes over and over and has to be
+//! polled again after each tick, until it returns `Pending` and so has a
+//! wakeup arranged for the next one.
+//!
+//! ```
+//! # use std::{cell::Cell, time::Duration};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{basics::sleep, two_ways::with_deadline::{auto, manual}};
+//!
+//! let logged = Cell::new(0);
+//! let log = |_| logged.set(logged.get() + 1);
+//! let work = || sleep(Duration::from_millis(50));
+//! let period = Duration::from_millis(10);
+//!
+//! let ((), ticks) = block_on(auto::with_deadline(period, work(), log));
+//! assert!((3..=6).contains(&ticks), "{ticks} ticks");
+//! assert_eq!(logged.replace(0), ticks);
+//!
+//! let ((), ticks) = block_on(manual::with_deadline(period, work(), log));
+//! assert!((3..=6).contains(&ticks), "{ticks} ticks");
+//! assert_eq!(logged.get(), ticks);
+//! ```
+
+pub mod auto {
+    use core::{future::Future, pin::pin, time::Duration};
+    use std::time::Instant;
+
+    use crate::{basics::interval::interval, either::Either};
+
+    pub async fn with_deadline<Fut, F>(
+        period: Duration,
+        fut: Fut,
+        mut on_tick: F,
+    ) -> (Fut::Output, u32)
+    where
+        Fut: Future,
+        F: FnMut(Instant),
+    {
+        let mut interval = interval(period);
+        let mut ticks = 0;
+        let mut fut = pin!(fut);
+        loop {
+            // `fut` goes first, so finishing right on a tick still wins.
+            let next =
+                futures_lite::future::or(async { Either::Left(fut.as_mut().await) }, async {
+                    Either::Right(interval.tick().await)
+                });
+            match next.await {
+                Either::Left(out) => return (out, ticks),
+                Either::Right(deadline) => {
+                    ticks += 1;
+                    on_tick(deadline);
+                }
+            }
+        }
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll, time::Duration};
+    use std::time::Instant;
+
+    use pin_project::pin_project;
+
+    use crate::basics::interval::{interval, Interval};
+
+    pub async fn with_deadline<Fut, F>(period: Duration, fut: Fut, on_tick: F) -> (Fut::Output, u32)
+    where
+        Fut: Future,
+        F: FnMut(Instant),
+    {
+        WithDeadline {
+            fut,
+            interval: interval(period),
+            ticks: 0,
+            on_tick,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct WithDeadline<Fut, F> {
+        #[pin]
+        fut: Fut,
+        #[pin]
+        interval: Interval,
+        ticks: u32,
+        on_tick: F,
+    }
+
+    impl<Fut, F> Future for WithDeadline<Fut, F>
+    where
+        Fut: Future,
+        F: FnMut(Instant),
+    {
+        type Output = (Fut::Output, u32);
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if let Poll::Ready(out) = this.fut.poll(cx) {
+                return Poll::Ready((out, *this.ticks));
+            }
+
+            // keep going until the interval is pending, both to catch up on
+            // any missed ticks and so that the next tick will wake us.
+            let interval = this.interval.get_mut();
+            while let Poll::Ready(deadline) = interval.poll_tick(cx) {
+                *this.ticks += 1;
+                (this.on_tick)(deadline);
+            }
+            Poll::Pending
+        }
+    }
+}
@@ -0,0 +1,95 @@
+//! Run two futures concurrently, resolving to a tuple of their outputs. Where
+//! [`join`](super::join) focuses on drop-safety of uninitialized slots, this is
+//! the minimal version: each output is parked in an [`Option`] until the other
+//! side catches up.
+//!
+//! A future which has already completed must not be polled again, even while
+//! the other side keeps the zip pending:
+//!
+//! ```
+//! # use std::{future::Future, pin::Pin, task::{Context, Poll}};
+//! # use futures_lite::future::block_on;
+//! use async_intuition::{testing::poll_counter, two_ways::zip::{auto, manual}};
+//!
+//! struct ReadyOnce(bool);
+//!
+//! impl Future for ReadyOnce {
+//!     type Output = u8;
+//!
+//!     fn poll(mut self: Pin<&mut Self>, _: &mut Context<'_>) -> Poll<u8> {
+//!         assert!(!self.0, "polled after returning `Ready`");
+//!         self.0 = true;
+//!         Poll::Ready(1)
+//!     }
+//! }
+//!
+//! assert_eq!(block_on(auto::zip(ReadyOnce(false), poll_counter(3, ()))), (1, ()));
+//! assert_eq!(block_on(manual::zip(ReadyOnce(false), poll_counter(3, ()))), (1, ()));
+//! ```
+
+pub mod auto {
+    use core::future::Future;
+
+    pub async fn zip<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+        futures_lite::future::zip(a, b).await
+    }
+}
+
+pub mod manual {
+    use core::{future::Future, pin::Pin, task::Poll};
+
+    use pin_project::pin_project;
+
+    pub async fn zip<A: Future, B: Future>(a: A, b: B) -> (A::Output, B::Output) {
+        Zip {
+            a,
+            b,
+            a_out: None,
+            b_out: None,
+            done: false,
+        }
+        .await
+    }
+
+    #[pin_project]
+    struct Zip<A: Future, B: Future> {
+        #[pin]
+        a: A,
+        #[pin]
+        b: B,
+        a_out: Option<A::Output>,
+        b_out: Option<B::Output>,
+        done: bool,
+    }
+
+    impl<A: Future, B: Future> Future for Zip<A, B> {
+        type Output = (A::Output, B::Output);
+
+        fn poll(self: Pin<&mut Self>, cx: &mut core::task::Context<'_>) -> Poll<Self::Output> {
+            let this = self.project();
+            if *this.done {
+                panic!("`async fn` resumed after completion");
+            }
+
+            // a stored output means that side already returned `Ready`, and
+            // polling it again is a contract violation.
+            if this.a_out.is_none() {
+                if let Poll::Ready(out) = this.a.poll(cx) {
+                    *this.a_out = Some(out);
+                }
+            }
+            if this.b_out.is_none() {
+                if let Poll::Ready(out) = this.b.poll(cx) {
+                    *this.b_out = Some(out);
+                }
+            }
+
+            if this.a_out.is_some() && this.b_out.is_some() {
+                *this.done = true;
+                Poll::Ready((this.a_out.take().unwrap(), this.b_out.take().unwrap()))
+            } else {
+                Poll::Pending
+            }
+        }
+    }
+}
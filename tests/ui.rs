@@ -0,0 +1,7 @@
+// the pinning guarantees from `pin_and_suffering`, checked by making sure the
+// code which would break them doesn't compile.
+#[test]
+fn ui() {
+    let t = trybuild::TestCases::new();
+    t.compile_fail("tests/ui/*.rs");
+}
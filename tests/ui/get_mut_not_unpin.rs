@@ -0,0 +1,16 @@
+// `Pin::get_mut` hands out a plain `&mut`, which could then be used to move
+// the future. So it's only available when the future is `Unpin`, which one
+// holding a reference across an `.await` never is.
+use std::pin::{pin, Pin};
+
+async fn self_ref() {
+    let a = 1;
+    let r = &a;
+    std::future::ready(()).await;
+    println!("{r}");
+}
+
+fn main() {
+    let fut = pin!(self_ref());
+    let _moved = Pin::get_mut(fut);
+}
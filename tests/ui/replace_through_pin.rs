@@ -0,0 +1,15 @@
+// `Pin` has no `DerefMut` for `!Unpin` targets, so there's no `&mut` to hand
+// to `mem::replace` (or `mem::take`, or `mem::swap`).
+use std::{mem, pin::pin};
+
+async fn self_ref() {
+    let a = 1;
+    let r = &a;
+    std::future::ready(()).await;
+    println!("{r}");
+}
+
+fn main() {
+    let fut = pin!(self_ref());
+    let _old = mem::replace(&mut *fut, self_ref());
+}